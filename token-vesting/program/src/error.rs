@@ -30,6 +30,57 @@ pub enum VestingError {
 
     #[error("Devesting has already signed by account !")]
     DevestingAlreadySigned,
+
+    #[error("Date oracle account does not match the one configured on the Vesting Type!")]
+    InvalidDateOracle,
+
+    #[error("Date oracle account has not been initialized yet!")]
+    DateOracleNotInitialized,
+
+    #[error("Vesting has already been revoked!")]
+    AlreadyRevoked,
+
+    #[error("Not enough signer approvals have been collected for this action!")]
+    InsufficientApprovals,
+
+    #[error("Vesting is not realized yet according to its realizor program!")]
+    UnrealizedVesting,
+
+    #[error("New token account's mint does not match the Vesting Type's pool mint!")]
+    TokenAccountMintMismatch,
+
+    #[error("Program is already on the Whitelist!")]
+    ProgramAlreadyWhitelisted,
+
+    #[error("Program is not on the Whitelist!")]
+    ProgramNotWhitelisted,
+
+    #[error("Whitelist has reached its maximum number of programs!")]
+    WhitelistFull,
+
+    #[error("Whitelisting this many tokens would exceed the Vesting Account's total!")]
+    WhitelistWithdrawalExceedsTotal,
+
+    #[error("Not enough whitelisted tokens to deposit back!")]
+    NotEnoughWhitelistedTokens,
+
+    #[error("Stale nonce: this approval was signed against a previous epoch of the Current Signers Account!")]
+    StaleNonce,
+
+    #[error("This Vesting Type was not created as revocable!")]
+    VestingTypeNotRevocable,
+
+    #[error("Vesting Type's reward is not realized yet according to its realizor program!")]
+    UnrealizedReward,
+
+    #[error("Not enough time has passed since the last withdrawal from this Vesting Account!")]
+    WithdrawalTimelocked,
+
+    #[error("This Vesting Type or Vesting Account configures a DateOracle or a realizor, neither of which WithdrawFromVestingBatch's fixed per-entry account layout can check; withdraw via WithdrawFromVesting instead!")]
+    BatchWithdrawalUnsupportedConfiguration,
+
+    #[error("Summing the requested token amounts overflowed a u64!")]
+    ArithmeticOverflow,
 }
 
 impl From<VestingError> for ProgramError {
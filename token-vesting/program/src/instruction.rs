@@ -1,7 +1,13 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::program_error::ProgramError::{self, InvalidInstructionData};
+use solana_program::pubkey::Pubkey;
+use spl_token::instruction::MAX_SIGNERS;
 
-use crate::state::{LinearVesting, MAX_VESTINGS};
+use crate::state::{LinearVesting, Realizor, TimeBasis, MAX_VESTINGS};
+
+/// Maximum number of beneficiaries a single `CreateVestingAccountsBatch`
+/// instruction may provision, mirroring `VestingSchedule::MAX_VESTINGS`.
+pub const MAX_BATCH_SIZE: usize = 16;
 
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
 pub enum VestingInstruction {
@@ -13,22 +19,81 @@ pub enum VestingInstruction {
     ///   1. `[writable]` Account to be initialized as Vesting Type Account
     ///   2. `[writable]` Token Account to be transferred as Pool Token Account
     ///   3. `[]` Token program account
+    ///
+    /// When `revocable` is `true`, the administrator may later call
+    /// `RevokeVestingType` to reclaim every not-yet-unlocked token out of
+    /// the pool.
+    ///
+    /// `time_basis` selects which `Clock` sysvar field `vestings`'
+    /// timestamps are measured against: `Timestamp` for the usual
+    /// `Clock::unix_timestamp`-based schedule, or `Slot` for deterministic,
+    /// block-production-aligned unlock boundaries (see `TimeBasis`).
+    ///
+    /// `withdrawal_timelock` additionally rate-limits claims independently
+    /// of the schedule itself: `WithdrawFromVesting` rejects a withdrawal
+    /// from a given Vesting Account unless at least `withdrawal_timelock`
+    /// seconds (measured against wall-clock time, regardless of
+    /// `time_basis`) have passed since that account's last withdrawal. Pass
+    /// `0` for no such limit.
     CreateVestingType {
         token_count: u64,
         vesting_count: u8,
         vestings: [(u64, LinearVesting); MAX_VESTINGS],
+        date_oracle: Option<Pubkey>,
+        revocable: bool,
+        time_basis: TimeBasis,
+        withdrawal_timelock: i64,
+    },
+
+    /// Initializes Vesting Type Account from an arbitrary, caller-supplied
+    /// list of `(unlock_timestamp, token_amount)` pairs, rather than a
+    /// pre-expanded table of `LinearVesting`s (see `CreateVestingType`).
+    /// Built internally via `ScheduleBuilder::from_unlock_points`, which
+    /// sorts `points` by timestamp and sums amounts for duplicate
+    /// timestamps; a mismatched total or zero-token entry is rejected with
+    /// `ScheduleIsNotValid`. Useful for front-ends that already generate a
+    /// flat release-date table (e.g. one unlock per month for two years).
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The fee payer account (future administrator)
+    ///   1. `[writable]` Account to be initialized as Vesting Type Account
+    ///   2. `[writable]` Token Account to be transferred as Pool Token Account
+    ///   3. `[]` Token program account
+    CreateVestingTypeFromUnlockPoints {
+        token_count: u64,
+        point_count: u8,
+        points: [(u64, u64); MAX_VESTINGS],
+        date_oracle: Option<Pubkey>,
+        revocable: bool,
+        time_basis: TimeBasis,
     },
 
     /// Creates Vesting Account for specific Vesting Type Account
     ///
     /// Accounts expected by this instruction:
     ///
-    ///   0. `[signer]` The fee payer account (administrator)
+    ///   0. `[signer]` The fee payer account (administrator, unless Required
+    ///      Signers Account configures a committee)
     ///   1. `[writable]` Vesting Type Account
     ///   2. `[writable]` Account to be initialized as Vesting Account
     ///   3. `[]` Vesting receiver token account
     ///   4. `[]` Pool Token Account for this Vesting Type Account
-    CreateVestingAccount { total_tokens: u64 },
+    ///   5. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///      never called for this Vesting Type, pass any uninitialized
+    ///      account and the administrator's signature alone authorizes the
+    ///      call; otherwise signers whose `weights` sum to at least
+    ///      `require_number` must have approved this exact instruction via
+    ///      `ApprovePrivilegedAction` first
+    ///
+    /// When `realizor` is `Some`, the Vesting Account additionally requires
+    /// its configured realizor program to approve every future withdrawal
+    /// (see `WithdrawFromVesting`), e.g. to keep tokens locked while the
+    /// beneficiary still has a staked balance elsewhere.
+    CreateVestingAccount {
+        total_tokens: u64,
+        realizor: Option<Realizor>,
+    },
 
     /// Calculates tokens using data from Vesting Type Account and Vesting Account,
     /// and transfers them to Associated Token Account in Vesting Account
@@ -39,6 +104,16 @@ pub enum VestingInstruction {
     ///   1. `[writable]` Vesting Type Account
     ///   2. `[]` Vesting Account
     ///   3. `[]` Token Program Account
+    ///   6. `[writable]` Withdrawal Log Account; pass any uninitialized
+    ///        account if `InitWithdrawalLog` was never called for this
+    ///        Vesting Type
+    ///   7.. `[]` Date Oracle Account, present only when the Vesting Type
+    ///        configures one; followed by `[]` Realizor Metadata Account and
+    ///        `[]` Realizor Program Account, present only when the Vesting
+    ///        Account configures a `realizor`; followed by `[]` Realizor
+    ///        Metadata Account and `[]` Realizor Program Account again,
+    ///        present only when the Vesting Type configures a `realizor`
+    ///        (see `SetRealizor`)
     WithdrawFromVesting { amount: u64 },
 
     /// Calculates non-locked tokens using data from Vesting Type Account and Pool Token Account,
@@ -46,27 +121,58 @@ pub enum VestingInstruction {
     ///
     /// Accounts expected by this instruction:
     ///
-    ///   0. `[signer]` The fee payer account (administrator)
+    ///   0. `[signer]` The fee payer account (administrator, unless Required
+    ///      Signers Account configures a committee)
     ///   1. `[writable]` Vesting receiver token account (associated account)
     ///   2. `[]` PDA
     ///   3. `[writable]` Pool Token Account for this Vesting Type Account
     ///   4. `[]` Vesting Type Account
     ///   5. `[]` Token Program Account
+    ///   6. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///      never called for this Vesting Type, pass any uninitialized
+    ///      account and the administrator's signature alone authorizes the
+    ///      call; otherwise signers whose `weights` sum to at least
+    ///      `require_number` must have approved this exact instruction via
+    ///      `ApprovePrivilegedAction` first
+    ///   7. `[writable]` Withdrawal Log Account; pass any uninitialized
+    ///        account if `InitWithdrawalLog` was never called for this
+    ///        Vesting Type
     WithdrawExcessiveFromPool { amount: u64 },
 
-    /// Changes Vesting Type Account schedule settings
+    /// Lets the administrator overwrite a Vesting Type Account's schedule
+    /// after `CreateVestingType`, since `CreateVestingType` itself rejects
+    /// any second initialization with `AlreadyInitialized` and would
+    /// otherwise leave a typo'd cliff or end-time permanently stuck. The new
+    /// schedule must still pass `is_valid()`, must keep `token_count` large
+    /// enough to cover `locked_tokens_amount` (tokens already committed to
+    /// investors must remain claimable), and must not reduce the amount
+    /// already unlocked as of the current timestamp. Also replaces
+    /// `withdrawal_timelock` (see `CreateVestingType`).
     ///
     /// Accounts expected by this instruction:
     ///
-    ///   0. `[signer]` The fee payer account (administrator)
+    ///   0. `[signer]` The fee payer account (administrator, unless Required
+    ///      Signers Account configures a committee)
     ///   1. `[writable]` Vesting Type Account
+    ///   2. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///      never called for this Vesting Type, pass any uninitialized
+    ///      account and the administrator's signature alone authorizes the
+    ///      call; otherwise signers whose `weights` sum to at least
+    ///      `require_number` must have approved this exact instruction via
+    ///      `ApprovePrivilegedAction` first
     ChangeVestingTypeSchedule {
         token_count: u64,
         vesting_count: u8,
         vestings: [(u64, LinearVesting); MAX_VESTINGS],
+        withdrawal_timelock: i64,
     },
 
-    /// Create multisig
+    /// Creates the devesting committee for a Vesting Type Account, reading
+    /// `require_signers`/`require_number`/`all_number` off an existing SPL
+    /// Token `Multisig` account and pairing each signer at the same index
+    /// with a `weights` entry, so `require_number` is interpreted by
+    /// `SignDevesting` as a cumulative weight threshold rather than a plain
+    /// signer count (e.g. a 2-weight founder plus 1-weight advisors).
     ///
     /// Accounts expected by this instruction:
     ///
@@ -74,9 +180,46 @@ pub enum VestingInstruction {
     ///   1. `[writable]` Vesting Type Account
     ///   2. `[writable]` Multisig Account
     ///   3. `[writable]` Signers Account
-    CreateMultisig,
+    CreateMultisig {
+        weights: [u8; MAX_SIGNERS],
+    },
 
-    /// Sign devesting
+    /// Registers an approval from one of the `require_signers` configured by
+    /// `CreateMultisig` for a pending privileged action (`CreateVestingAccount`,
+    /// `CreateVestingAccountsBatch`, `ChangeVestingTypeSchedule`,
+    /// `WithdrawExcessiveFromPool`, `AddToWhitelist`, `RemoveFromWhitelist`,
+    /// or an administrator-authorized `ReassignVestingBeneficiary` or
+    /// `WhitelistWithdraw`), identified
+    /// by `action_hash` (see `RequiredSigners::action_hash`). Approving a
+    /// different action than the one currently pending discards whatever
+    /// approvals had already been collected, since those were given for
+    /// different arguments. Once the `weights` of the distinct signers who
+    /// have approved sum to at least `require_number`, the gated instruction
+    /// may be submitted and executes without itself needing to be signed by
+    /// an administrator.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` One of the configured `require_signers`
+    ///   1. `[writable]` Required Signers Account
+    ApprovePrivilegedAction { action_hash: [u8; 32] },
+
+    /// Collects one signature towards the devesting committee's cumulative
+    /// weight threshold: the signer's entry in `RequiredSigners::weights` is
+    /// added to the running total once their bit is set, rather than simply
+    /// counting signers, so committee members can carry different voting
+    /// power (e.g. a 2-weight founder plus 1-weight advisors). `nonce` must
+    /// match the Current Signers Account's stored nonce, which is bumped on
+    /// every successful call, so a previously collected approval can't be
+    /// replayed against a re-initialized signer account for the same
+    /// vesting. Once the weight threshold is met, the Vesting Account is
+    /// closed and its rent reclaimed; the unvested remainder still sitting
+    /// in the Pool Token Account (`total_tokens - withdrawn_tokens -
+    /// whitelisted_tokens`) is transferred back to a treasury token account
+    /// designated by the administrator, and `locked_tokens_amount` is
+    /// decremented accordingly. This makes the devesting committee a
+    /// genuine revocation mechanism rather than one that strands the
+    /// unvested balance in the pool.
     ///
     /// Accounts expected by this instruction:
     ///
@@ -85,7 +228,228 @@ pub enum VestingInstruction {
     ///   2. `[writable]` Required Signers Account
     ///   3. `[writable]` Vesting Account which will be deleted
     ///   4. `[writable]` Vesting Type Account
-    SignDevesting,
+    ///   5. `[writable]` Pool Token Account for this Vesting Type Account
+    ///   6. `[writable]` Treasury Token Account to receive the unvested remainder
+    ///   7. `[]` PDA
+    ///   8. `[]` Token Program Account
+    SignDevesting { nonce: u64 },
+
+    /// Writes a trusted, off-chain published timestamp into a Date Oracle Account.
+    /// Vesting Type Accounts that configure `date_oracle` use this timestamp in
+    /// place of the Clock sysvar when computing unlocked tokens.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The oracle authority (first call initializes the account)
+    ///   1. `[writable]` Date Oracle Account
+    UpdateDateOracle { timestamp: i64 },
+
+    /// Revokes a `VestingAccount` before it has fully vested. Tokens already
+    /// unlocked at the current timestamp remain claimable by the beneficiary
+    /// via `WithdrawFromVesting`; the unvested remainder is transferred back
+    /// out of the Pool Token Account and the account is frozen so no further
+    /// tokens accrue.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The fee payer account (administrator)
+    ///   1. `[writable]` Vesting Type Account
+    ///   2. `[writable]` Vesting Account to terminate
+    ///   3. `[writable]` Pool Token Account for this Vesting Type Account
+    ///   4. `[writable]` Destination Token Account to receive the unvested remainder
+    ///   5. `[]` PDA
+    ///   6. `[]` Token Program Account
+    TerminateVesting,
+
+    /// Creates many Vesting Accounts for the same Vesting Type Account in one
+    /// atomic instruction, amortizing the repeated deserialize/validate of
+    /// the shared Vesting Type and Pool Token Account across a whole tranche
+    /// rather than paying that cost once per transaction. `NotEnoughTokensInPool`
+    /// is checked once against the summed total of every entry, and
+    /// `locked_tokens_amount` is updated with a single write; a failure on
+    /// any entry fails the whole instruction, so no partial tranche is ever
+    /// committed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The fee payer account (administrator, unless Required
+    ///      Signers Account configures a committee)
+    ///   1. `[writable]` Vesting Type Account
+    ///   2. `[]` Pool Token Account for this Vesting Type Account
+    ///   3. `[]` Token program account
+    ///   4. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///      never called for this Vesting Type, pass any uninitialized
+    ///      account and the administrator's signature alone authorizes the
+    ///      call; otherwise signers whose `weights` sum to at least
+    ///      `require_number` must have approved this exact instruction via
+    ///      `ApprovePrivilegedAction` first
+    ///   5.. `[writable]` Account to be initialized as Vesting Account, followed
+    ///        by `[]` the matching receiver Token Account, repeated once per entry
+    CreateVestingAccountsBatch {
+        entries_count: u8,
+        entries: [(Pubkey, u64); MAX_BATCH_SIZE],
+    },
+
+    /// Rewrites the `token_account` a `VestingAccount` pays out to, leaving
+    /// `total_tokens`/`withdrawn_tokens` untouched. Authorized either by the
+    /// beneficiary of record (the owner of the current receiver token
+    /// account) or by the vesting-type administrator, subject to the same
+    /// committee gating as other privileged actions.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The beneficiary of record, or the administrator
+    ///      (unless Required Signers Account configures a committee)
+    ///   1. `[]` Vesting Type Account
+    ///   2. `[writable]` Vesting Account
+    ///   3. `[]` Pool Token Account for this Vesting Type Account
+    ///   4. `[]` Current receiver Token Account
+    ///   5. `[]` New receiver Token Account
+    ///   6. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///      never called for this Vesting Type, pass any uninitialized
+    ///      account; a beneficiary-authorized call never needs it approved
+    ReassignVestingBeneficiary,
+
+    /// Approves `program` to receive still-locked tokens via
+    /// `WhitelistWithdraw` (first call initializes the Whitelist Account).
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The fee payer account (administrator, unless Required
+    ///      Signers Account configures a committee)
+    ///   1. `[]` Vesting Type Account
+    ///   2. `[writable]` Whitelist Account
+    ///   3. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///      never called for this Vesting Type, pass any uninitialized
+    ///      account and the administrator's signature alone authorizes the
+    ///      call; otherwise signers whose `weights` sum to at least
+    ///      `require_number` must have approved this exact instruction via
+    ///      `ApprovePrivilegedAction` first
+    AddToWhitelist { program: Pubkey },
+
+    /// Revokes a previously whitelisted program's ability to receive further
+    /// `WhitelistWithdraw` transfers. Tokens it already holds are unaffected
+    /// and may still be returned via `WhitelistDeposit`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The fee payer account (administrator, unless Required
+    ///      Signers Account configures a committee)
+    ///   1. `[]` Vesting Type Account
+    ///   2. `[writable]` Whitelist Account
+    ///   3. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///      never called for this Vesting Type, pass any uninitialized
+    ///      account and the administrator's signature alone authorizes the
+    ///      call; otherwise signers whose `weights` sum to at least
+    ///      `require_number` must have approved this exact instruction via
+    ///      `ApprovePrivilegedAction` first
+    RemoveFromWhitelist { program: Pubkey },
+
+    /// Moves still-locked tokens out of the Pool Token Account into a token
+    /// account owned by a whitelisted program, e.g. to stake them, without
+    /// that movement counting as a real withdrawal: `amount` is added to
+    /// `whitelisted_tokens` instead of `withdrawn_tokens`, so the tokens
+    /// remain bounded by the Vesting Account's unlocked schedule and can
+    /// never be claimed twice.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The beneficiary of record, or the administrator
+    ///      (unless Required Signers Account configures a committee)
+    ///   1. `[]` Vesting Type Account
+    ///   2. `[writable]` Vesting Account
+    ///   3. `[writable]` Pool Token Account for this Vesting Type Account
+    ///   4. `[]` Current receiver Token Account
+    ///   5. `[writable]` Destination Token Account, owned by the whitelisted program
+    ///   6. `[]` The whitelisted program, whose key must be present in Whitelist Account
+    ///   7. `[]` Whitelist Account
+    ///   8. `[]` PDA
+    ///   9. `[]` Token Program Account
+    ///   10. `[writable]` Required Signers Account. If `CreateMultisig` was
+    ///       never called for this Vesting Type, pass any uninitialized
+    ///       account; a beneficiary-authorized call never needs it approved
+    WhitelistWithdraw { amount: u64 },
+
+    /// Reverses a prior `WhitelistWithdraw`, returning tokens from the
+    /// whitelisted program back into the Pool Token Account and decrementing
+    /// `whitelisted_tokens`. Authorized by whoever currently holds signing
+    /// authority over the source token account, forwarded via CPI.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` Authority over the source Token Account
+    ///   1. `[]` Vesting Type Account
+    ///   2. `[writable]` Vesting Account
+    ///   3. `[writable]` Source Token Account, owned by the whitelisted program
+    ///   4. `[writable]` Pool Token Account for this Vesting Type Account
+    ///   5. `[]` Token Program Account
+    WhitelistDeposit { amount: u64 },
+
+    /// Withdraws from many Vesting Accounts sharing the same Vesting Type in
+    /// one atomic instruction, amortizing the repeated deserialize/validate
+    /// of the shared Vesting Type and Pool Token Account, and the final
+    /// `locked_tokens_amount` update, across a whole batch. Does not support
+    /// a configured `DateOracle` or a per-entry `realizor`, since either
+    /// would require extra accounts per pair; use `WithdrawFromVesting` for
+    /// those Vesting Accounts instead.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Vesting Type Account
+    ///   1. `[writable]` Pool Token Account for this Vesting Type Account
+    ///   2. `[]` PDA
+    ///   3. `[]` Token Program Account
+    ///   4.. `[writable]` Vesting Account, followed by `[writable]` the
+    ///        matching receiver Token Account, repeated once per entry in
+    ///        `amounts`
+    WithdrawFromVestingBatch { amounts: Vec<u64> },
+
+    /// Reclaims every not-yet-unlocked token out of a `revocable` Vesting
+    /// Type's pool, e.g. when a project needs to pull back an unvested
+    /// allocation. Computes `locked_tokens_amount - already_vested -
+    /// whitelisted_tokens_amount` as of the `Clock` sysvar and transfers it,
+    /// PDA-signed, to a destination token account chosen by the
+    /// administrator; `locked_tokens_amount` is set to the vested amount and
+    /// the Vesting Type is marked `is_revoked`, which makes a second
+    /// `RevokeVestingType` fail with `AlreadyRevoked`. The withdrawal
+    /// instructions do not check `is_revoked`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` The fee payer account (administrator)
+    ///   1. `[writable]` Vesting Type Account
+    ///   2. `[writable]` Pool Token Account for this Vesting Type Account
+    ///   3. `[writable]` Destination Token Account to receive the reclaimed tokens
+    ///   4. `[]` PDA
+    ///   5. `[]` Token Program Account
+    RevokeVestingType,
+
+    /// Configures a `realizor` on a Vesting Type, gating every subsequent
+    /// `WithdrawFromVesting` against it in addition to any realizor already
+    /// configured on the individual Vesting Account — e.g. to require an
+    /// external reward program to confirm a milestone before any Vesting
+    /// Account under this Vesting Type can withdraw. Passing `None` clears a
+    /// previously configured realizor.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` Administrator of the Vesting Type Account
+    ///   1. `[writable]` Vesting Type Account
+    SetRealizor { realizor: Option<Realizor> },
+
+    /// Initializes the append-only audit log read and written by
+    /// `WithdrawFromVesting` and `WithdrawExcessiveFromPool`. Once
+    /// initialized for a Vesting Type, the same Withdrawal Log Account must
+    /// be passed to every withdrawal against it; a Vesting Type that never
+    /// calls this can still pass any uninitialized account in that slot and
+    /// nothing is recorded.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[signer]` Administrator of the Vesting Type Account
+    ///   1. `[]` Vesting Type Account
+    ///   2. `[writable]` Withdrawal Log Account
+    InitWithdrawalLog,
 }
 
 impl VestingInstruction {
@@ -111,17 +475,36 @@ mod test {
             token_count: 1_000_000,
             vesting_count: 2,
             vestings,
+            date_oracle: None,
+            revocable: false,
+            time_basis: TimeBasis::Timestamp,
+            withdrawal_timelock: 604_800,
         };
         let packed_create = original_create.pack();
         let unpacked_create = VestingInstruction::unpack(&packed_create).unwrap();
         assert_eq!(original_create, unpacked_create);
 
-        let original_unlock = VestingInstruction::CreateVestingAccount { total_tokens: 400 };
+        let original_unlock = VestingInstruction::CreateVestingAccount {
+            total_tokens: 400,
+            realizor: None,
+        };
         assert_eq!(
             original_unlock,
             VestingInstruction::unpack(&original_unlock.pack()).unwrap()
         );
 
+        let original_unlock_realizor = VestingInstruction::CreateVestingAccount {
+            total_tokens: 400,
+            realizor: Some(Realizor {
+                program: Pubkey::new_unique(),
+                metadata: Pubkey::new_unique(),
+            }),
+        };
+        assert_eq!(
+            original_unlock_realizor,
+            VestingInstruction::unpack(&original_unlock_realizor.pack()).unwrap()
+        );
+
         let original_init = VestingInstruction::WithdrawExcessiveFromPool { amount: 4000 };
         assert_eq!(
             original_init,
@@ -137,10 +520,42 @@ mod test {
             token_count: 1_000_000,
             vesting_count: 2,
             vestings,
+            withdrawal_timelock: 0,
         };
         assert_eq!(
             original_change,
             VestingInstruction::unpack(&original_change.pack()).unwrap()
         );
+
+        let original_approve = VestingInstruction::ApprovePrivilegedAction {
+            action_hash: [7; 32],
+        };
+        assert_eq!(
+            original_approve,
+            VestingInstruction::unpack(&original_approve.pack()).unwrap()
+        );
+
+        let original_set_realizor = VestingInstruction::SetRealizor { realizor: None };
+        assert_eq!(
+            original_set_realizor,
+            VestingInstruction::unpack(&original_set_realizor.pack()).unwrap()
+        );
+
+        let original_set_realizor_some = VestingInstruction::SetRealizor {
+            realizor: Some(Realizor {
+                program: Pubkey::new_unique(),
+                metadata: Pubkey::new_unique(),
+            }),
+        };
+        assert_eq!(
+            original_set_realizor_some,
+            VestingInstruction::unpack(&original_set_realizor_some.pack()).unwrap()
+        );
+
+        let original_init_withdrawal_log = VestingInstruction::InitWithdrawalLog;
+        assert_eq!(
+            original_init_withdrawal_log,
+            VestingInstruction::unpack(&original_init_withdrawal_log.pack()).unwrap()
+        );
     }
 }
@@ -1,7 +1,6 @@
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 
-pub mod builder;
 pub mod error;
 pub mod instruction;
 pub mod state;
@@ -1,13 +1,27 @@
+pub mod add_to_whitelist;
+pub mod approve_privileged_action;
 pub mod change_vesting_type_schedule;
 pub mod close_vesting_account;
 pub mod create_multisig;
 pub mod create_vesting_account;
+pub mod create_vesting_accounts_batch;
 pub mod create_vesting_type;
+pub mod create_vesting_type_from_unlock_points;
+pub mod init_withdrawal_log;
+pub mod reassign_vesting_beneficiary;
+pub mod remove_from_whitelist;
+pub mod revoke_vesting_type;
+pub mod set_realizor;
 pub mod sign_devesting;
+pub mod terminate_vesting;
 #[cfg(test)]
 mod tests;
+pub mod update_date_oracle;
+pub mod whitelist_deposit;
+pub mod whitelist_withdraw;
 pub mod withdraw_excessive_from_pool;
 pub mod withdraw_from_vesting;
+pub mod withdraw_from_vesting_batch;
 use crate::instruction::VestingInstruction;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
 pub struct Processor {}
@@ -26,6 +40,10 @@ impl Processor {
                 token_count,
                 vesting_count,
                 vestings,
+                date_oracle,
+                revocable,
+                time_basis,
+                withdrawal_timelock,
             } => {
                 msg!("Instruction: Create Vesting Type");
                 Self::create_vesting_type(
@@ -33,11 +51,43 @@ impl Processor {
                     accounts,
                     token_count,
                     &vestings[..vesting_count as usize],
+                    date_oracle,
+                    revocable,
+                    time_basis,
+                    withdrawal_timelock,
                 )
             }
-            VestingInstruction::CreateVestingAccount { total_tokens } => {
+            VestingInstruction::CreateVestingTypeFromUnlockPoints {
+                token_count,
+                point_count,
+                points,
+                date_oracle,
+                revocable,
+                time_basis,
+            } => {
+                msg!("Instruction: Create Vesting Type From Unlock Points");
+                Self::create_vesting_type_from_unlock_points(
+                    program_id,
+                    accounts,
+                    token_count,
+                    &points[..point_count as usize],
+                    date_oracle,
+                    revocable,
+                    time_basis,
+                )
+            }
+            VestingInstruction::CreateVestingAccount {
+                total_tokens,
+                realizor,
+            } => {
                 msg!("Instruction: Create Vesting");
-                Self::create_vesting_account(program_id, accounts, total_tokens)
+                Self::create_vesting_account(
+                    program_id,
+                    accounts,
+                    total_tokens,
+                    realizor,
+                    instruction_data,
+                )
             }
             VestingInstruction::WithdrawFromVesting { amount } => {
                 msg!("Instruction: Withdraw From Vesting");
@@ -45,26 +95,89 @@ impl Processor {
             }
             VestingInstruction::WithdrawExcessiveFromPool { amount } => {
                 msg!("Instruction: Withdraw Excessive From Pool");
-                Self::withdraw_excessive_from_pool(program_id, accounts, amount)
+                Self::withdraw_excessive_from_pool(program_id, accounts, amount, instruction_data)
             }
             VestingInstruction::ChangeVestingTypeSchedule {
                 token_count,
                 vesting_count,
                 vestings,
+                withdrawal_timelock,
             } => {
                 msg!("Instruction: Change Vesting Type Schedule");
-                panic!("Changing vesting type is forbidden")
-                // Self::change_vesting_type_schedule(
-                //     program_id, accounts,
-                //     token_count,
-                //     &vestings[..vesting_count as usize],
-                // )
-            }
-            VestingInstruction::CreateMultisig => {
-                Processor::create_multisig(program_id, accounts, instruction_data)
-            }
-            VestingInstruction::SignDevesting => {
-                Processor::sign_devesting(program_id, accounts, instruction_data)
+                Self::change_vesting_type_schedule(
+                    program_id,
+                    accounts,
+                    token_count,
+                    &vestings[..vesting_count as usize],
+                    withdrawal_timelock,
+                    instruction_data,
+                )
+            }
+            VestingInstruction::CreateMultisig { weights } => {
+                Processor::create_multisig(program_id, accounts, weights)
+            }
+            VestingInstruction::ApprovePrivilegedAction { action_hash } => {
+                msg!("Instruction: Approve Privileged Action");
+                Self::approve_privileged_action(program_id, accounts, action_hash)
+            }
+            VestingInstruction::SignDevesting { nonce } => {
+                Processor::sign_devesting(program_id, accounts, nonce)
+            }
+            VestingInstruction::UpdateDateOracle { timestamp } => {
+                msg!("Instruction: Update Date Oracle");
+                Self::update_date_oracle(program_id, accounts, timestamp)
+            }
+            VestingInstruction::TerminateVesting => {
+                msg!("Instruction: Terminate Vesting");
+                Self::terminate_vesting(program_id, accounts)
+            }
+            VestingInstruction::CreateVestingAccountsBatch {
+                entries_count,
+                entries,
+            } => {
+                msg!("Instruction: Create Vesting Accounts Batch");
+                Self::create_vesting_accounts_batch(
+                    program_id,
+                    accounts,
+                    &entries[..entries_count as usize],
+                    instruction_data,
+                )
+            }
+            VestingInstruction::ReassignVestingBeneficiary => {
+                msg!("Instruction: Reassign Vesting Beneficiary");
+                Self::reassign_vesting_beneficiary(program_id, accounts, instruction_data)
+            }
+            VestingInstruction::AddToWhitelist { program } => {
+                msg!("Instruction: Add To Whitelist");
+                Self::add_to_whitelist(program_id, accounts, program, instruction_data)
+            }
+            VestingInstruction::RemoveFromWhitelist { program } => {
+                msg!("Instruction: Remove From Whitelist");
+                Self::remove_from_whitelist(program_id, accounts, program, instruction_data)
+            }
+            VestingInstruction::WhitelistWithdraw { amount } => {
+                msg!("Instruction: Whitelist Withdraw");
+                Self::whitelist_withdraw(program_id, accounts, amount, instruction_data)
+            }
+            VestingInstruction::WhitelistDeposit { amount } => {
+                msg!("Instruction: Whitelist Deposit");
+                Self::whitelist_deposit(program_id, accounts, amount)
+            }
+            VestingInstruction::WithdrawFromVestingBatch { amounts } => {
+                msg!("Instruction: Withdraw From Vesting Batch");
+                Self::withdraw_from_vesting_batch(program_id, accounts, &amounts)
+            }
+            VestingInstruction::RevokeVestingType => {
+                msg!("Instruction: Revoke Vesting Type");
+                Self::revoke_vesting_type(program_id, accounts)
+            }
+            VestingInstruction::SetRealizor { realizor } => {
+                msg!("Instruction: Set Realizor");
+                Self::set_realizor(program_id, accounts, realizor)
+            }
+            VestingInstruction::InitWithdrawalLog => {
+                msg!("Instruction: Init Withdrawal Log");
+                Self::init_withdrawal_log(program_id, accounts)
             }
         }
     }
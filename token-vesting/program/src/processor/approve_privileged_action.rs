@@ -0,0 +1,45 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    state::RequiredSigners,
+    utils::{read_from_storage, write_to_storage},
+};
+
+use super::Processor;
+
+impl Processor {
+    pub fn approve_privileged_action(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        action_hash: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let signer = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut required_signers_data =
+            read_from_storage::<RequiredSigners>(required_signers_account)?;
+
+        validate_required_signers(&required_signers_data)?;
+
+        required_signers_data.approve(signer.key, action_hash)?;
+        write_to_storage(required_signers_data, required_signers_account)
+    }
+}
+
+fn validate_required_signers(required_signers_data: &RequiredSigners) -> ProgramResult {
+    if !required_signers_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    Ok(())
+}
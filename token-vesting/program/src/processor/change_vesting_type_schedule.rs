@@ -1,9 +1,10 @@
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 
 use std::convert::TryFrom;
@@ -11,14 +12,15 @@ use std::convert::TryFrom;
 use super::Processor;
 use crate::{
     error::VestingError,
-    state::{VestingSchedule, VestingTypeAccount},
-    utils::write_to_storage,
+    state::{LinearVesting, RequiredSigners, VestingSchedule, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
 };
 
 #[derive(Clone, Copy)]
 struct Accounts<'a, 'b> {
     signer: &'a AccountInfo<'b>,
     vesting_type: &'a AccountInfo<'b>,
+    required_signers_account: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> TryFrom<&'a [AccountInfo<'b>]> for Accounts<'a, 'b> {
@@ -29,6 +31,7 @@ impl<'a, 'b> TryFrom<&'a [AccountInfo<'b>]> for Accounts<'a, 'b> {
 
         let signer = next_account_info(account_info_iter)?;
         let vesting_type = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
 
         if !signer.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
@@ -37,6 +40,7 @@ impl<'a, 'b> TryFrom<&'a [AccountInfo<'b>]> for Accounts<'a, 'b> {
         Ok(Accounts {
             signer,
             vesting_type,
+            required_signers_account,
         })
     }
 }
@@ -45,45 +49,122 @@ impl Processor {
     pub fn change_vesting_type_schedule(
         _program_id: &Pubkey,
         accounts: &[AccountInfo],
-        initial_unlock: u64,
-        start_time: u64,
-        end_time: u64,
-        unlock_period: u64,
-        cliff: u64,
+        token_count: u64,
+        vestings: &[(u64, LinearVesting)],
+        withdrawal_timelock: i64,
+        instruction_data: &[u8],
     ) -> ProgramResult {
         let Accounts {
             signer,
             vesting_type,
+            required_signers_account,
         } = Accounts::try_from(accounts)?;
 
-        let new_vesting_schedule = VestingSchedule {
-            initial_unlock,
-            start_time,
-            end_time,
-            unlock_period,
-            cliff,
-        };
-
-        // check if the old schedule exists
-        let mut vesting_type_data =
-            VestingTypeAccount::try_from_slice(&vesting_type.data.borrow())?;
-        if !vesting_type_data.is_initialized {
-            return Err(VestingError::NotInitialized.into());
-        }
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
 
-        // check administrator
-        if &vesting_type_data.administrator != signer.key {
-            return Err(VestingError::NotAdministrator.into());
-        }
+        // The schedule's time basis is fixed at `CreateVestingType`/
+        // `CreateVestingTypeFromUnlockPoints` time and carried over here, so a
+        // schedule change can't silently move a Vesting Type from
+        // slot-aligned to wall-clock unlocks (or vice versa).
+        let time_basis = vesting_type_data.vesting_schedule.time_basis();
+        let new_vesting_schedule = VestingSchedule::new(token_count, vestings, time_basis);
+
+        let committee = validate_vesting_type(
+            &vesting_type_data,
+            vesting_type,
+            signer,
+            required_signers_account,
+            instruction_data,
+        )?;
+        validate_still_fully_backed(&vesting_type_data, &new_vesting_schedule)?;
 
-        // check data for new schedule
         if !new_vesting_schedule.is_valid() {
             return Err(VestingError::ScheduleIsNotValid.into());
         }
 
-        // change the old schedule to the new one
+        let now = vesting_type_data.vesting_schedule.now(&Clock::get()?);
+        validate_no_clawback(&vesting_type_data, &new_vesting_schedule, now)?;
+
         vesting_type_data.vesting_schedule = new_vesting_schedule;
+        vesting_type_data.withdrawal_timelock = withdrawal_timelock;
+
+        write_to_storage(vesting_type_data, vesting_type)?;
 
-        write_to_storage(vesting_type_data, vesting_type)
+        if let Some(mut required_signers_data) = committee {
+            required_signers_data.clear_pending_action();
+            write_to_storage(required_signers_data, required_signers_account)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Authorizes the call either against the single `administrator`, or, once a
+/// committee has been configured via `CreateMultisig`, against a pending
+/// approval of this exact instruction collected through
+/// `ApprovePrivilegedAction`. Returns the committee's `RequiredSigners` so the
+/// caller can clear the consumed approval once the schedule change commits.
+fn validate_vesting_type(
+    vesting_type_data: &VestingTypeAccount,
+    vesting_type: &AccountInfo,
+    signer: &AccountInfo,
+    required_signers_account: &AccountInfo,
+    instruction_data: &[u8],
+) -> Result<Option<RequiredSigners>, ProgramError> {
+    if !vesting_type_data.is_initialized {
+        return Err(VestingError::NotInitialized.into());
     }
+
+    let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
+    if !required_signers_data.is_initialized {
+        if &vesting_type_data.administrator != signer.key {
+            return Err(VestingError::NotAdministrator.into());
+        }
+
+        return Ok(None);
+    }
+
+    if required_signers_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let action = RequiredSigners::action_hash(vesting_type.key, instruction_data);
+    if !required_signers_data.is_approved(action) {
+        return Err(VestingError::InsufficientApprovals.into());
+    }
+
+    Ok(Some(required_signers_data))
+}
+
+// Already-created `VestingAccount`s are only ever backed up to
+// `locked_tokens_amount` tokens. A new schedule must keep that amount fully
+// claimable, otherwise existing beneficiaries would be able to unlock more
+// tokens than the pool actually reserves for them.
+fn validate_still_fully_backed(
+    vesting_type_data: &VestingTypeAccount,
+    new_vesting_schedule: &VestingSchedule,
+) -> ProgramResult {
+    if new_vesting_schedule.token_count() < vesting_type_data.locked_tokens_amount {
+        return Err(VestingError::ScheduleIsNotValid.into());
+    }
+
+    Ok(())
+}
+
+// Every already-created `VestingAccount`'s claimable amount is derived from
+// the Vesting Type's own schedule (`VestingSchedule::available`), so the
+// amount unlocked as of `now` must never go down, otherwise a beneficiary
+// who could already withdraw those tokens would see them locked again.
+fn validate_no_clawback(
+    vesting_type_data: &VestingTypeAccount,
+    new_vesting_schedule: &VestingSchedule,
+    now: u64,
+) -> ProgramResult {
+    let previously_unlocked = vesting_type_data.vesting_schedule.available(now);
+    let newly_unlocked = new_vesting_schedule.available(now);
+    if newly_unlocked < previously_unlocked {
+        return Err(VestingError::ScheduleIsNotValid.into());
+    }
+
+    Ok(())
 }
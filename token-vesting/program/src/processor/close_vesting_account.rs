@@ -1,11 +1,10 @@
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
 };
 
 use crate::{
     state::{VestingAccount, VestingTypeAccount},
-    utils::write_to_storage,
+    utils::{read_from_storage, write_to_storage},
 };
 
 use super::Processor;
@@ -14,9 +13,8 @@ impl Processor {
     pub fn close_vesting_account(accounts: (&AccountInfo, &AccountInfo)) -> ProgramResult {
         let (vesting_type, vesting) = accounts;
 
-        let mut vesting_type_data =
-            VestingTypeAccount::try_from_slice(&vesting_type.data.borrow())?;
-        let mut vesting_data = VestingAccount::try_from_slice(&vesting.data.borrow())?;
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
 
         validate_vesting_type(&vesting_type_data)?;
         validate_vesting(&vesting_data, vesting_type)?;
@@ -1,4 +1,3 @@
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -6,12 +5,12 @@ use solana_program::{
     program_pack::Pack,
     pubkey::Pubkey,
 };
-use spl_token::state::Multisig;
+use spl_token::{instruction::MAX_SIGNERS, state::Multisig};
 
 use crate::{
     error::VestingError,
     state::{RequiredSigners, VestingTypeAccount},
-    utils::write_to_storage,
+    utils::{read_from_storage, write_to_storage},
 };
 
 use super::Processor;
@@ -20,7 +19,7 @@ impl Processor {
     pub fn create_multisig(
         _program_id: &Pubkey,
         accounts: &[AccountInfo],
-        _instruction_data: &[u8],
+        weights: [u8; MAX_SIGNERS],
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let signer = next_account_info(account_info_iter)?;
@@ -32,9 +31,9 @@ impl Processor {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let vesting_type_data = VestingTypeAccount::try_from_slice(&vesting_type.data.borrow())?;
+        let vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
         let mut required_signers_data =
-            RequiredSigners::try_from_slice(&required_signers_account.data.borrow())?;
+            read_from_storage::<RequiredSigners>(required_signers_account)?;
         let multisig_data = Multisig::unpack(&multisig_account.data.borrow())?;
 
         validate_signers(&required_signers_data)?;
@@ -44,6 +43,8 @@ impl Processor {
         required_signers_data.all_number = multisig_data.n;
         required_signers_data.require_number = multisig_data.m;
         required_signers_data.require_signers = multisig_data.signers;
+        required_signers_data.weights = weights;
+        required_signers_data.is_initialized = true;
         write_to_storage(required_signers_data, required_signers_account)
     }
 }
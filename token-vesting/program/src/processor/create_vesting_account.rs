@@ -1,4 +1,3 @@
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -12,8 +11,8 @@ use spl_token::state::Account as TokenAccount;
 use super::Processor;
 use crate::{
     error::VestingError,
-    state::{VestingAccount, VestingTypeAccount},
-    utils::write_to_storage,
+    state::{Realizor, RequiredSigners, VestingAccount, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
 };
 
 impl Processor {
@@ -21,6 +20,8 @@ impl Processor {
         _program_id: &Pubkey,
         accounts: &[AccountInfo],
         total_tokens: u64,
+        realizor: Option<Realizor>,
+        instruction_data: &[u8],
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
@@ -29,19 +30,25 @@ impl Processor {
         let vesting = next_account_info(account_info_iter)?;
         let token_account = next_account_info(account_info_iter)?;
         let token_pool = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
 
         if !signer.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let mut vesting_type_data =
-            VestingTypeAccount::try_from_slice(&vesting_type.data.borrow())?;
-        let mut vesting_data = VestingAccount::try_from_slice(&vesting.data.borrow())?;
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
         let token_account_data = TokenAccount::unpack(&token_account.data.borrow())?;
         let token_pool_data = TokenAccount::unpack(&token_pool.data.borrow())?;
 
         validate_vesting(vesting, &vesting_data)?;
-        validate_vesting_type(&vesting_type_data, signer)?;
+        let committee = validate_vesting_type(
+            &vesting_type_data,
+            vesting_type,
+            signer,
+            required_signers_account,
+            instruction_data,
+        )?;
         validate_token_pool(token_pool, &vesting_type_data)?;
         validate_token_account(token_account, &token_account_data, &token_pool_data)?;
         check_enough_tokens(&vesting_type_data, &token_pool_data, total_tokens)?;
@@ -51,10 +58,18 @@ impl Processor {
         vesting_data.withdrawn_tokens = 0;
         vesting_data.token_account = *token_account.key;
         vesting_data.vesting_type_account = *vesting_type.key;
+        vesting_data.realizor = realizor;
         write_to_storage(vesting_data, vesting)?;
 
         vesting_type_data.locked_tokens_amount += total_tokens;
-        write_to_storage(vesting_type_data, vesting_type)
+        write_to_storage(vesting_type_data, vesting_type)?;
+
+        if let Some(mut required_signers_data) = committee {
+            required_signers_data.clear_pending_action();
+            write_to_storage(required_signers_data, required_signers_account)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -71,19 +86,41 @@ fn validate_vesting(vesting: &AccountInfo, vesting_data: &VestingAccount) -> Pro
     Ok(())
 }
 
+/// Authorizes the call either against the single `administrator`, or, once a
+/// committee has been configured via `CreateMultisig`, against a pending
+/// approval of this exact instruction collected through
+/// `ApprovePrivilegedAction`. Returns the committee's `RequiredSigners` so the
+/// caller can clear the consumed approval once the vesting account commits.
 fn validate_vesting_type(
     vesting_type_data: &VestingTypeAccount,
+    vesting_type: &AccountInfo,
     signer: &AccountInfo,
-) -> ProgramResult {
+    required_signers_account: &AccountInfo,
+    instruction_data: &[u8],
+) -> Result<Option<RequiredSigners>, ProgramError> {
     if !vesting_type_data.is_initialized {
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if &vesting_type_data.administrator != signer.key {
-        return Err(VestingError::NotAdministrator.into());
+    let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
+    if !required_signers_data.is_initialized {
+        if &vesting_type_data.administrator != signer.key {
+            return Err(VestingError::NotAdministrator.into());
+        }
+
+        return Ok(None);
+    }
+
+    if required_signers_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let action = RequiredSigners::action_hash(vesting_type.key, instruction_data);
+    if !required_signers_data.is_approved(action) {
+        return Err(VestingError::InsufficientApprovals.into());
     }
 
-    Ok(())
+    Ok(Some(required_signers_data))
 }
 
 fn validate_token_pool(
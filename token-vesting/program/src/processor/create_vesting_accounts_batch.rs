@@ -0,0 +1,147 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use spl_token::state::Account as TokenAccount;
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::{RequiredSigners, VestingAccount, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn create_vesting_accounts_batch(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        entries: &[(Pubkey, u64)],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let token_pool = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if token_program.key != &spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let token_pool_data = TokenAccount::unpack(&token_pool.data.borrow())?;
+
+        let committee = validate_vesting_type(
+            &vesting_type_data,
+            vesting_type,
+            signer,
+            required_signers_account,
+            instruction_data,
+        )?;
+        if token_pool.key != &vesting_type_data.token_pool {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let total_tokens: u64 = entries.iter().try_fold(0u64, |acc, (_, entry_total_tokens)| {
+            acc.checked_add(*entry_total_tokens)
+                .ok_or(VestingError::ArithmeticOverflow)
+        })?;
+        let locked_after_batch = vesting_type_data
+            .locked_tokens_amount
+            .checked_add(total_tokens)
+            .ok_or(VestingError::ArithmeticOverflow)?;
+        if locked_after_batch > token_pool_data.amount {
+            return Err(VestingError::NotEnoughTokensInPool.into());
+        }
+
+        let rent = Rent::get()?;
+        for &(receiver_token_account, entry_total_tokens) in entries.iter() {
+            let vesting = next_account_info(account_info_iter)?;
+            let token_account = next_account_info(account_info_iter)?;
+
+            if token_account.key != &receiver_token_account {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
+            if vesting_data.is_initialized {
+                return Err(VestingError::AlreadyInitialized.into());
+            }
+            if !rent.is_exempt(vesting.lamports(), vesting.data_len()) {
+                return Err(VestingError::NotRentExempt.into());
+            }
+
+            let token_account_data = TokenAccount::unpack(&token_account.data.borrow())?;
+            if token_account.owner != &spl_token::id() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            if token_account_data.mint != token_pool_data.mint {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            vesting_data.is_initialized = true;
+            vesting_data.total_tokens = entry_total_tokens;
+            vesting_data.withdrawn_tokens = 0;
+            vesting_data.token_account = *token_account.key;
+            vesting_data.vesting_type_account = *vesting_type.key;
+            write_to_storage(vesting_data, vesting)?;
+        }
+
+        vesting_type_data.locked_tokens_amount += total_tokens;
+        write_to_storage(vesting_type_data, vesting_type)?;
+
+        if let Some(mut required_signers_data) = committee {
+            required_signers_data.clear_pending_action();
+            write_to_storage(required_signers_data, required_signers_account)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Authorizes the call either against the single `administrator`, or, once a
+/// committee has been configured via `CreateMultisig`, against a pending
+/// approval of this exact instruction collected through
+/// `ApprovePrivilegedAction`. Returns the committee's `RequiredSigners` so the
+/// caller can clear the consumed approval once the batch commits.
+fn validate_vesting_type(
+    vesting_type_data: &VestingTypeAccount,
+    vesting_type: &AccountInfo,
+    signer: &AccountInfo,
+    required_signers_account: &AccountInfo,
+    instruction_data: &[u8],
+) -> Result<Option<RequiredSigners>, ProgramError> {
+    if !vesting_type_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
+    if !required_signers_data.is_initialized {
+        if &vesting_type_data.administrator != signer.key {
+            return Err(VestingError::NotAdministrator.into());
+        }
+
+        return Ok(None);
+    }
+
+    if required_signers_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let action = RequiredSigners::action_hash(vesting_type.key, instruction_data);
+    if !required_signers_data.is_approved(action) {
+        return Err(VestingError::InsufficientApprovals.into());
+    }
+
+    Ok(Some(required_signers_data))
+}
@@ -1,4 +1,3 @@
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -14,8 +13,8 @@ use std::convert::TryFrom;
 use super::Processor;
 use crate::{
     error::VestingError,
-    state::{LinearVesting, VestingSchedule, VestingTypeAccount},
-    utils::write_to_storage,
+    state::{LinearVesting, TimeBasis, VestingSchedule, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
 };
 
 #[derive(Clone, Copy)]
@@ -56,11 +55,21 @@ impl Processor {
         accounts: &[AccountInfo],
         token_count: u64,
         vestings: &[(u64, LinearVesting)],
+        date_oracle: Option<Pubkey>,
+        revocable: bool,
+        time_basis: TimeBasis,
+        withdrawal_timelock: i64,
     ) -> ProgramResult {
         let accounts = Accounts::try_from(accounts)?;
 
-        let vesting_schedule = VestingSchedule::new(token_count, &vestings);
-        check_and_initialize_vesting_type(accounts, vesting_schedule)?;
+        let vesting_schedule = VestingSchedule::new(token_count, &vestings, time_basis);
+        check_and_initialize_vesting_type(
+            accounts,
+            vesting_schedule,
+            date_oracle,
+            revocable,
+            withdrawal_timelock,
+        )?;
         check_and_transfer_token_pool(program_id, accounts)
     }
 }
@@ -68,6 +77,9 @@ impl Processor {
 fn check_and_initialize_vesting_type(
     accounts: Accounts,
     vesting_schedule: VestingSchedule,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    withdrawal_timelock: i64,
 ) -> ProgramResult {
     let Accounts {
         signer,
@@ -76,7 +88,7 @@ fn check_and_initialize_vesting_type(
         ..
     } = accounts;
 
-    let mut vesting_type_data = VestingTypeAccount::try_from_slice(&vesting_type.data.borrow())?;
+    let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
     if vesting_type_data.is_initialized {
         return Err(VestingError::AlreadyInitialized.into());
     }
@@ -95,6 +107,10 @@ fn check_and_initialize_vesting_type(
     vesting_type_data.locked_tokens_amount = 0;
     vesting_type_data.administrator = *signer.key;
     vesting_type_data.token_pool = *token_pool.key;
+    vesting_type_data.date_oracle = date_oracle;
+    vesting_type_data.revocable = revocable;
+    vesting_type_data.is_revoked = false;
+    vesting_type_data.withdrawal_timelock = withdrawal_timelock;
 
     write_to_storage(vesting_type_data, vesting_type)
 }
@@ -0,0 +1,61 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::VestingError,
+    state::{VestingTypeAccount, WithdrawalLog},
+    utils::{read_from_storage, write_to_storage},
+};
+
+use super::Processor;
+
+impl Processor {
+    pub fn init_withdrawal_log(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let withdrawal_log_account = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut withdrawal_log_data = read_from_storage::<WithdrawalLog>(withdrawal_log_account)?;
+
+        validate_vesting_type(&vesting_type_data, signer)?;
+        validate_withdrawal_log(&withdrawal_log_data)?;
+
+        withdrawal_log_data.is_initialized = true;
+        withdrawal_log_data.vesting_type_account = *vesting_type.key;
+        write_to_storage(withdrawal_log_data, withdrawal_log_account)
+    }
+}
+
+fn validate_vesting_type(
+    vesting_type_data: &VestingTypeAccount,
+    signer: &AccountInfo,
+) -> ProgramResult {
+    if !vesting_type_data.is_initialized {
+        return Err(VestingError::NotInitialized.into());
+    }
+
+    if &vesting_type_data.administrator != signer.key {
+        return Err(VestingError::NotAdministrator.into());
+    }
+
+    Ok(())
+}
+
+fn validate_withdrawal_log(withdrawal_log_data: &WithdrawalLog) -> ProgramResult {
+    if withdrawal_log_data.is_initialized {
+        return Err(VestingError::AlreadyInitialized.into());
+    }
+
+    Ok(())
+}
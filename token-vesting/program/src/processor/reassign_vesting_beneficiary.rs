@@ -0,0 +1,154 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use spl_token::state::Account as TokenAccount;
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::{RequiredSigners, VestingAccount, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn reassign_vesting_beneficiary(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let vesting = next_account_info(account_info_iter)?;
+        let token_pool = next_account_info(account_info_iter)?;
+        let current_token_account = next_account_info(account_info_iter)?;
+        let new_token_account = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
+        let token_pool_data = TokenAccount::unpack(&token_pool.data.borrow())?;
+        let current_token_account_data = TokenAccount::unpack(&current_token_account.data.borrow())?;
+        let new_token_account_data = TokenAccount::unpack(&new_token_account.data.borrow())?;
+
+        validate_vesting(&vesting_data, vesting_type, current_token_account)?;
+        validate_token_pool(token_pool, &vesting_type_data)?;
+        let committee = validate_authority(
+            &vesting_type_data,
+            vesting_type,
+            signer,
+            &current_token_account_data,
+            required_signers_account,
+            instruction_data,
+        )?;
+        validate_new_token_account(new_token_account, &new_token_account_data, &token_pool_data)?;
+
+        vesting_data.token_account = *new_token_account.key;
+        write_to_storage(vesting_data, vesting)?;
+
+        if let Some(mut required_signers_data) = committee {
+            required_signers_data.clear_pending_action();
+            write_to_storage(required_signers_data, required_signers_account)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_vesting(
+    vesting_data: &VestingAccount,
+    vesting_type: &AccountInfo,
+    current_token_account: &AccountInfo,
+) -> ProgramResult {
+    if !vesting_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if &vesting_data.vesting_type_account != vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if &vesting_data.token_account != current_token_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+fn validate_token_pool(
+    token_pool: &AccountInfo,
+    vesting_type_data: &VestingTypeAccount,
+) -> ProgramResult {
+    if token_pool.key != &vesting_type_data.token_pool {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+/// Authorizes the call either against the beneficiary of record (the owner
+/// of the current receiver token account, who may always redirect their own
+/// grant), or, falling back to the same administrator/committee gating as
+/// other privileged actions. Returns the committee's `RequiredSigners` only
+/// when the administrator path consumed a pending committee approval.
+fn validate_authority(
+    vesting_type_data: &VestingTypeAccount,
+    vesting_type: &AccountInfo,
+    signer: &AccountInfo,
+    current_token_account_data: &TokenAccount,
+    required_signers_account: &AccountInfo,
+    instruction_data: &[u8],
+) -> Result<Option<RequiredSigners>, ProgramError> {
+    if &current_token_account_data.owner == signer.key {
+        return Ok(None);
+    }
+
+    if !vesting_type_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
+    if !required_signers_data.is_initialized {
+        if &vesting_type_data.administrator != signer.key {
+            return Err(VestingError::NotAdministrator.into());
+        }
+
+        return Ok(None);
+    }
+
+    if required_signers_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let action = RequiredSigners::action_hash(vesting_type.key, instruction_data);
+    if !required_signers_data.is_approved(action) {
+        return Err(VestingError::InsufficientApprovals.into());
+    }
+
+    Ok(Some(required_signers_data))
+}
+
+fn validate_new_token_account(
+    new_token_account: &AccountInfo,
+    new_token_account_data: &TokenAccount,
+    token_pool_data: &TokenAccount,
+) -> ProgramResult {
+    if new_token_account.owner != &spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if new_token_account_data.mint != token_pool_data.mint {
+        return Err(VestingError::TokenAccountMintMismatch.into());
+    }
+
+    Ok(())
+}
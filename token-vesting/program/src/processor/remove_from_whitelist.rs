@@ -0,0 +1,104 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::{RequiredSigners, VestingTypeAccount, Whitelist},
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn remove_from_whitelist(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        program: Pubkey,
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let whitelist = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut whitelist_data = read_from_storage::<Whitelist>(whitelist)?;
+
+        let committee = validate_vesting_type(
+            &vesting_type_data,
+            vesting_type,
+            signer,
+            required_signers_account,
+            instruction_data,
+        )?;
+        validate_whitelist(&whitelist_data, vesting_type)?;
+
+        whitelist_data.remove(&program)?;
+        write_to_storage(whitelist_data, whitelist)?;
+
+        if let Some(mut required_signers_data) = committee {
+            required_signers_data.clear_pending_action();
+            write_to_storage(required_signers_data, required_signers_account)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Authorizes the call either against the single `administrator`, or, once a
+/// committee has been configured via `CreateMultisig`, against a pending
+/// approval of this exact instruction collected through
+/// `ApprovePrivilegedAction`. Returns the committee's `RequiredSigners` so the
+/// caller can clear the consumed approval once the whitelist update commits.
+fn validate_vesting_type(
+    vesting_type_data: &VestingTypeAccount,
+    vesting_type: &AccountInfo,
+    signer: &AccountInfo,
+    required_signers_account: &AccountInfo,
+    instruction_data: &[u8],
+) -> Result<Option<RequiredSigners>, ProgramError> {
+    if !vesting_type_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
+    if !required_signers_data.is_initialized {
+        if &vesting_type_data.administrator != signer.key {
+            return Err(VestingError::NotAdministrator.into());
+        }
+
+        return Ok(None);
+    }
+
+    if required_signers_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let action = RequiredSigners::action_hash(vesting_type.key, instruction_data);
+    if !required_signers_data.is_approved(action) {
+        return Err(VestingError::InsufficientApprovals.into());
+    }
+
+    Ok(Some(required_signers_data))
+}
+
+fn validate_whitelist(whitelist_data: &Whitelist, vesting_type: &AccountInfo) -> ProgramResult {
+    if !whitelist_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if whitelist_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
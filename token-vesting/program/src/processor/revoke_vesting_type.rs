@@ -0,0 +1,119 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::VestingTypeAccount,
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn revoke_vesting_type(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let token_pool = next_account_info(account_info_iter)?;
+        let destination = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let (pda, bump_seed) =
+            Pubkey::find_program_address(&[vesting_type.key.as_ref()], program_id);
+
+        validate_vesting_type(&vesting_type_data, signer)?;
+        validate_pda_account(pda_account, &pda)?;
+        validate_token_program_account(token_program)?;
+
+        let now = vesting_type_data.vesting_schedule.now(&Clock::get()?);
+        let vested = vesting_type_data
+            .vesting_schedule
+            .available(now)
+            .min(vesting_type_data.locked_tokens_amount);
+        // Tokens staked out via `WhitelistWithdraw` have already left
+        // `token_pool`, so they must be excluded from what's reclaimed here.
+        let unvested = vesting_type_data
+            .locked_tokens_amount
+            .saturating_sub(vested)
+            .saturating_sub(vesting_type_data.whitelisted_tokens_amount);
+
+        vesting_type_data.locked_tokens_amount = vested;
+        vesting_type_data.is_revoked = true;
+        write_to_storage(vesting_type_data, vesting_type)?;
+
+        if unvested > 0 {
+            let transfer_tokens_ix = spl_token::instruction::transfer(
+                token_program.key,
+                token_pool.key,
+                destination.key,
+                &pda,
+                &[&pda],
+                unvested,
+            )?;
+            invoke_signed(
+                &transfer_tokens_ix,
+                &[
+                    token_pool.clone(),
+                    destination.clone(),
+                    pda_account.clone(),
+                    token_program.clone(),
+                ],
+                &[&[vesting_type.key.as_ref(), &[bump_seed]]],
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_vesting_type(
+    vesting_type_data: &VestingTypeAccount,
+    signer: &AccountInfo,
+) -> ProgramResult {
+    if !vesting_type_data.is_initialized {
+        return Err(VestingError::NotInitialized.into());
+    }
+
+    if &vesting_type_data.administrator != signer.key {
+        return Err(VestingError::NotAdministrator.into());
+    }
+
+    if !vesting_type_data.revocable {
+        return Err(VestingError::VestingTypeNotRevocable.into());
+    }
+
+    if vesting_type_data.is_revoked {
+        return Err(VestingError::AlreadyRevoked.into());
+    }
+
+    Ok(())
+}
+
+fn validate_pda_account(pda_account: &AccountInfo, pda: &Pubkey) -> ProgramResult {
+    if pda_account.key != pda {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+fn validate_token_program_account(token_program: &AccountInfo) -> ProgramResult {
+    if token_program.key != &spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::{Realizor, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn set_realizor(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        realizor: Option<Realizor>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        validate_vesting_type(&vesting_type_data, signer)?;
+
+        vesting_type_data.realizor = realizor;
+        write_to_storage(vesting_type_data, vesting_type)
+    }
+}
+
+fn validate_vesting_type(
+    vesting_type_data: &VestingTypeAccount,
+    signer: &AccountInfo,
+) -> ProgramResult {
+    if !vesting_type_data.is_initialized {
+        return Err(VestingError::NotInitialized.into());
+    }
+
+    if &vesting_type_data.administrator != signer.key {
+        return Err(VestingError::NotAdministrator.into());
+    }
+
+    Ok(())
+}
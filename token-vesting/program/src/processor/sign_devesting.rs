@@ -1,7 +1,7 @@
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
 };
@@ -9,8 +9,8 @@ use spl_token::instruction::MAX_SIGNERS;
 
 use crate::{
     error::VestingError,
-    state::{CurrentSigners, RequiredSigners},
-    utils::write_to_storage,
+    state::{CurrentSigners, RequiredSigners, VestingAccount},
+    utils::{read_from_storage, write_to_storage},
 };
 
 use super::Processor;
@@ -19,7 +19,7 @@ impl Processor {
     pub fn sign_devesting(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        _instruction_data: &[u8],
+        nonce: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let signer = next_account_info(account_info_iter)?;
@@ -27,11 +27,14 @@ impl Processor {
         let required_signers_account = next_account_info(account_info_iter)?;
         let deleted_vesting = next_account_info(account_info_iter)?;
         let vesting_type = next_account_info(account_info_iter)?;
+        let token_pool = next_account_info(account_info_iter)?;
+        let treasury_token_account = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
 
-        let required_signers_data =
-            RequiredSigners::try_from_slice(&required_signers_account.data.borrow())?;
+        let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
 
-        let mut signers_data = CurrentSigners::try_from_slice(&signers_account.data.borrow())?;
+        let mut signers_data = read_from_storage::<CurrentSigners>(signers_account)?;
 
         let index = required_signers_data
             .require_signers
@@ -42,15 +45,50 @@ impl Processor {
         validate_required_signers(&required_signers_data, vesting_type)?;
         validate_current_signers(&signers_data, deleted_vesting)?;
         validate_signer(signer, &signers_data, index)?;
+        validate_nonce(&signers_data, nonce)?;
+        signers_data.current_signers[index] = true;
 
         let closing_vesting = validate_signers(
             &signers_data.current_signers,
+            &required_signers_data.weights,
             required_signers_data.require_number,
         );
 
         signers_data.current_signers[index] = true;
-        
+        signers_data.nonce += 1;
+
         if closing_vesting {
+            validate_token_program_account(token_program)?;
+            let (pda, bump_seed) =
+                Pubkey::find_program_address(&[vesting_type.key.as_ref()], program_id);
+            validate_pda_account(pda_account, &pda)?;
+
+            let vesting_data = read_from_storage::<VestingAccount>(deleted_vesting)?;
+            let unvested = vesting_data.total_tokens
+                - vesting_data.withdrawn_tokens
+                - vesting_data.whitelisted_tokens;
+
+            if unvested > 0 {
+                let transfer_tokens_ix = spl_token::instruction::transfer(
+                    token_program.key,
+                    token_pool.key,
+                    treasury_token_account.key,
+                    &pda,
+                    &[&pda],
+                    unvested,
+                )?;
+                invoke_signed(
+                    &transfer_tokens_ix,
+                    &[
+                        token_pool.clone(),
+                        treasury_token_account.clone(),
+                        pda_account.clone(),
+                        token_program.clone(),
+                    ],
+                    &[&[vesting_type.key.as_ref(), &[bump_seed]]],
+                )?;
+            }
+
             Processor::close_vesting_account((vesting_type, deleted_vesting))?;
             let vesting_type_starting_lamports = vesting_type.lamports();
             **vesting_type.lamports.borrow_mut() = vesting_type_starting_lamports
@@ -64,14 +102,18 @@ impl Processor {
     }
 }
 
-pub fn validate_signers(current_signers: &[bool], require: u8) -> bool {
-    let num_signers = current_signers
+pub fn validate_signers(current_signers: &[bool], weights: &[u8; MAX_SIGNERS], require: u8) -> bool {
+    let total_weight: u32 = current_signers
         .iter()
-        .fold(0, |sum, sign| if *sign { sum + 1 } else { sum });
-    if num_signers < require {
-        return false;
-    }
-    true
+        .zip(weights.iter())
+        .fold(0, |sum, (signed, weight)| {
+            if *signed {
+                sum + *weight as u32
+            } else {
+                sum
+            }
+        });
+    total_weight >= require as u32
 }
 
 fn validate_required_signers(
@@ -119,3 +161,30 @@ fn validate_signer(
 
     Ok(())
 }
+
+/// Binds this call to the Current Signers Account's current epoch, so a
+/// previously collected approval can't be replayed against a re-initialized
+/// signer account for the same vesting.
+fn validate_nonce(signers_data: &CurrentSigners, nonce: u64) -> ProgramResult {
+    if nonce != signers_data.nonce {
+        return Err(VestingError::StaleNonce.into());
+    }
+
+    Ok(())
+}
+
+fn validate_pda_account(pda_account: &AccountInfo, pda: &Pubkey) -> ProgramResult {
+    if pda_account.key != pda {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+fn validate_token_program_account(token_program: &AccountInfo) -> ProgramResult {
+    if token_program.key != &spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
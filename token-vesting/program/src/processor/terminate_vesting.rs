@@ -0,0 +1,121 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::{VestingAccount, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn terminate_vesting(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let vesting = next_account_info(account_info_iter)?;
+        let token_pool = next_account_info(account_info_iter)?;
+        let destination = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
+        let (pda, bump_seed) =
+            Pubkey::find_program_address(&[vesting_type.key.as_ref()], program_id);
+
+        validate_vesting_type(&vesting_type_data, signer)?;
+        validate_vesting(&vesting_data, vesting_type)?;
+        validate_pda_account(pda_account, &pda)?;
+        validate_token_program_account(token_program)?;
+
+        let now = vesting_type_data.vesting_schedule.now(&Clock::get()?);
+        let unvested = vesting_data.terminate(&vesting_type_data.vesting_schedule, now);
+        write_to_storage(vesting_data, vesting)?;
+
+        vesting_type_data.locked_tokens_amount -= unvested;
+        write_to_storage(vesting_type_data, vesting_type)?;
+
+        if unvested > 0 {
+            let transfer_tokens_ix = spl_token::instruction::transfer(
+                token_program.key,
+                token_pool.key,
+                destination.key,
+                &pda,
+                &[&pda],
+                unvested,
+            )?;
+            invoke_signed(
+                &transfer_tokens_ix,
+                &[
+                    token_pool.clone(),
+                    destination.clone(),
+                    pda_account.clone(),
+                    token_program.clone(),
+                ],
+                &[&[vesting_type.key.as_ref(), &[bump_seed]]],
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_vesting_type(
+    vesting_type_data: &VestingTypeAccount,
+    signer: &AccountInfo,
+) -> ProgramResult {
+    if !vesting_type_data.is_initialized {
+        return Err(VestingError::NotInitialized.into());
+    }
+
+    if &vesting_type_data.administrator != signer.key {
+        return Err(VestingError::NotAdministrator.into());
+    }
+
+    Ok(())
+}
+
+fn validate_vesting(vesting_data: &VestingAccount, vesting_type: &AccountInfo) -> ProgramResult {
+    if !vesting_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if &vesting_data.vesting_type_account != vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if vesting_data.revoked {
+        return Err(VestingError::AlreadyRevoked.into());
+    }
+
+    Ok(())
+}
+
+fn validate_pda_account(pda_account: &AccountInfo, pda: &Pubkey) -> ProgramResult {
+    if pda_account.key != pda {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+fn validate_token_program_account(token_program: &AccountInfo) -> ProgramResult {
+    if token_program.key != &spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+mod change_vesting_type_schedule;
+mod common;
+mod create_vesting_account;
+mod create_vesting_type;
+mod reassign_vesting_beneficiary;
+mod revoke_vesting_type;
+mod terminate_vesting;
+mod whitelist;
+mod withdraw_excessive_from_pool;
+mod withdraw_from_vesting;
+mod withdraw_from_vesting_batch;
@@ -1,6 +1,6 @@
 use chrono::Utc;
 
-// use borsh::BorshSerialize;
+use borsh::BorshSerialize;
 use solana_program::{
     hash::Hash,
     instruction::{AccountMeta, Instruction, InstructionError},
@@ -9,15 +9,19 @@ use solana_program::{
 };
 use solana_program_test::ProgramTest;
 use solana_sdk::{
-    signature::Keypair, signature::Signer, system_instruction, transaction::Transaction,
-    transport::TransportError,
+    account::Account, signature::Keypair, signature::Signer, system_instruction,
+    transaction::Transaction, transport::TransportError,
 };
 use spl_token::{
     self,
-    instruction::{initialize_account, initialize_mint},
+    instruction::{initialize_account, initialize_mint, MAX_SIGNERS},
 };
 
-use crate::state::{LinearVesting, ScheduleBuilderError, VestingTypeAccount, MAX_VESTINGS};
+use crate::state::{
+    LinearVesting, RequiredSigners, ScheduleBuilderError, TimeBasis, VestingTypeAccount,
+    MAX_VESTINGS,
+};
+use crate::utils::Versioned;
 use crate::{instruction::VestingInstruction, state::VestingSchedule};
 
 use super::common::{add_account, deserialize_account, AbstractTestContext, ErrorChecker};
@@ -26,6 +30,7 @@ struct KeyPairs {
     mint: Keypair,
     vesting_type: Keypair,
     token_pool: Keypair,
+    required_signers: Keypair,
 }
 
 impl Default for KeyPairs {
@@ -34,6 +39,7 @@ impl Default for KeyPairs {
             mint: Keypair::new(),
             vesting_type: Keypair::new(),
             token_pool: Keypair::new(),
+            required_signers: Keypair::new(),
         }
     }
 }
@@ -42,6 +48,7 @@ type TestContext = AbstractTestContext<KeyPairs>;
 
 fn default_add_accounts(program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs) {
     add_account::<VestingTypeAccount>(program_test, program_id, &keypairs.vesting_type, true);
+    add_account::<RequiredSigners>(program_test, program_id, &keypairs.required_signers, true);
 }
 
 fn mint_init_transaction(
@@ -197,6 +204,10 @@ async fn call_create_vesting_type(
         token_count: vesting_schedule.token_count(),
         vesting_count: vesting_schedule.vestings().len() as u8,
         vestings,
+        date_oracle: None,
+        revocable: false,
+        time_basis: TimeBasis::Timestamp,
+        withdrawal_timelock: 0,
     }
     .pack();
     let mut accounts = vec![
@@ -229,7 +240,12 @@ async fn call_change_vesting_type_schedule(
         banks_client,
         recent_blockhash,
         payer,
-        keypairs: KeyPairs { vesting_type, .. },
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                required_signers,
+                ..
+            },
     } = test_context;
 
     let mut vestings: [(u64, LinearVesting); MAX_VESTINGS] = Default::default();
@@ -240,11 +256,13 @@ async fn call_change_vesting_type_schedule(
         token_count: new_vesting_schedule.token_count(),
         vesting_count: new_vesting_schedule.vestings().len() as u8,
         vestings,
+        withdrawal_timelock: 0,
     }
     .pack();
     let mut accounts = vec![
         AccountMeta::new(payer.pubkey(), true),
         AccountMeta::new(vesting_type.pubkey(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
     ];
     for (index, account_info) in account_overrides.into_iter() {
         accounts[index] = account_info;
@@ -288,6 +306,32 @@ async fn test_successful_change_vesting_type_schedule() {
     assert_eq!(vesting_type_data.vesting_schedule, new_vesting_schedule);
 }
 
+#[tokio::test]
+async fn test_change_vesting_type_schedule_with_clawback() {
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(&mut test_context).await;
+
+    let dt = Utc::now();
+    let timestamp = dt.timestamp() as u64;
+    let vesting_schedule = VestingSchedule::with_tokens(1000)
+        .legacy(timestamp - 100, timestamp + 200, 10, timestamp - 100, 0, None)
+        .unwrap()
+        .build()
+        .unwrap();
+    call_create_vesting_type(&mut test_context, &vesting_schedule, vec![])
+        .await
+        .unwrap();
+
+    // The old schedule already has tokens unlocked (cliff is in the past), so
+    // pushing the new cliff into the future would claw those back.
+    let new_vesting_schedule =
+        construct_new_vesting_schedule(200, 400, 20, 240, 200, 1000).unwrap();
+    let result =
+        call_change_vesting_type_schedule(&mut test_context, &new_vesting_schedule, vec![]).await;
+
+    ErrorChecker::from(result).check(InstructionError::Custom(2));
+}
+
 #[tokio::test]
 async fn test_change_vesting_type_schedule_with_invalid_schedule() {
     let mut test_context = TestContext::new(default_add_accounts).await;
@@ -318,6 +362,50 @@ async fn test_change_vesting_type_schedule_with_uninitialized_account() {
     ErrorChecker::from(result).check(InstructionError::Custom(5));
 }
 
-#[ignore = "Requires multiple signers!"]
 #[tokio::test]
-async fn test_change_vesting_type_schedule_without_administrator() {}
+async fn test_change_vesting_type_schedule_without_administrator() {
+    let signer_a = Keypair::new();
+    let signer_b = Keypair::new();
+
+    let add_accounts = |program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs| {
+        add_account::<VestingTypeAccount>(program_test, program_id, &keypairs.vesting_type, true);
+
+        let mut require_signers: [Pubkey; MAX_SIGNERS] = Default::default();
+        require_signers[0] = signer_a.pubkey();
+        require_signers[1] = signer_b.pubkey();
+        let mut required_signers = RequiredSigners {
+            is_initialized: true,
+            require_signers,
+            require_number: 2,
+            all_number: 2,
+            vesting_type_account: keypairs.vesting_type.pubkey(),
+            ..Default::default()
+        };
+        required_signers.set_version(RequiredSigners::VERSION);
+        let data = required_signers.try_to_vec().unwrap();
+        program_test.add_account(
+            keypairs.required_signers.pubkey(),
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                owner: program_id,
+                data,
+                ..Account::default()
+            },
+        );
+    };
+
+    let mut test_context = TestContext::new(add_accounts).await;
+    init_token_accounts(&mut test_context).await;
+
+    let vesting_schedule = construct_default_vesting_schedule();
+    call_create_vesting_type(&mut test_context, &vesting_schedule, vec![])
+        .await
+        .unwrap();
+
+    let new_vesting_schedule =
+        construct_new_vesting_schedule(200, 400, 20, 240, 200, 1000).unwrap();
+    let result =
+        call_change_vesting_type_schedule(&mut test_context, &new_vesting_schedule, vec![]).await;
+
+    ErrorChecker::from(result).check(InstructionError::Custom(12));
+}
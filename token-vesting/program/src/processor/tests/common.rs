@@ -12,6 +12,7 @@ use solana_sdk::{
 use spl_token::state::Account as TokenAccount;
 
 use crate::entrypoint::process_instruction;
+use crate::utils::Versioned;
 
 pub struct ErrorChecker {
     result: Result<(), TransportError>,
@@ -73,13 +74,15 @@ impl<Keys> AbstractTestContext<Keys> {
     }
 }
 
-pub fn add_account<DataType: Default + BorshSerialize>(
+pub fn add_account<DataType: Default + BorshSerialize + Versioned>(
     program_test: &mut ProgramTest,
     owner: Pubkey,
     account: &Keypair,
     rent_exempt: bool,
 ) {
-    let data = DataType::default().try_to_vec().unwrap();
+    let mut state = DataType::default();
+    state.set_version(DataType::VERSION);
+    let data = state.try_to_vec().unwrap();
     let rent = Rent::default();
     program_test.add_account(
         account.pubkey(),
@@ -1,5 +1,6 @@
 use chrono::Utc;
 
+use borsh::BorshSerialize;
 use solana_program::{
     hash::Hash,
     instruction::{AccountMeta, Instruction, InstructionError},
@@ -14,12 +15,13 @@ use solana_sdk::{
 };
 use spl_token::{
     self,
-    instruction::{initialize_account, initialize_mint, mint_to},
+    instruction::{initialize_account, initialize_mint, mint_to, MAX_SIGNERS},
     state::{Account as TokenAccount, AccountState},
 };
 
 use crate::instruction::VestingInstruction;
-use crate::state::{LinearVesting, VestingAccount, VestingSchedule, VestingTypeAccount};
+use crate::state::{LinearVesting, RequiredSigners, TimeBasis, VestingAccount, VestingSchedule, VestingTypeAccount};
+use crate::utils::Versioned;
 
 use super::common::{add_account, deserialize_account, AbstractTestContext, ErrorChecker};
 
@@ -29,6 +31,7 @@ struct KeyPairs {
     vesting: Keypair,
     token_account: Keypair,
     token_pool: Keypair,
+    required_signers: Keypair,
 }
 
 impl Default for KeyPairs {
@@ -39,6 +42,7 @@ impl Default for KeyPairs {
             vesting: Keypair::new(),
             token_account: Keypair::new(),
             token_pool: Keypair::new(),
+            required_signers: Keypair::new(),
         }
     }
 }
@@ -48,6 +52,7 @@ type TestContext = AbstractTestContext<KeyPairs>;
 fn default_add_accounts(program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs) {
     add_account::<VestingTypeAccount>(program_test, program_id, &keypairs.vesting_type, true);
     add_account::<VestingAccount>(program_test, program_id, &keypairs.vesting, true);
+    add_account::<RequiredSigners>(program_test, program_id, &keypairs.required_signers, true);
 }
 
 fn mint_init_transaction(
@@ -228,6 +233,10 @@ async fn call_create_vesting_type(
         token_count: vesting_schedule.token_count(),
         vesting_count: vesting_schedule.vestings().len() as u8,
         vestings,
+        date_oracle: None,
+        revocable: false,
+        time_basis: TimeBasis::Timestamp,
+        withdrawal_timelock: 0,
     }
     .pack();
     let mut accounts = vec![
@@ -266,17 +275,19 @@ async fn call_create_vesting(
                 vesting,
                 token_account,
                 token_pool,
+                required_signers,
                 ..
             },
     } = test_context;
 
-    let data = VestingInstruction::CreateVestingAccount { total_tokens }.pack();
+    let data = VestingInstruction::CreateVestingAccount { total_tokens, realizor: None }.pack();
     let mut accounts = vec![
         AccountMeta::new(payer.pubkey(), true),
         AccountMeta::new(vesting_type.pubkey(), false),
         AccountMeta::new(vesting.pubkey(), false),
         AccountMeta::new_readonly(token_account.pubkey(), false),
         AccountMeta::new(token_pool.pubkey(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
     ];
     for (index, account_info) in account_overrides.into_iter() {
         accounts[index] = account_info;
@@ -292,6 +303,146 @@ async fn call_create_vesting(
     banks_client.process_transaction(transaction).await
 }
 
+async fn call_create_vesting_accounts_batch(
+    test_context: &mut TestContext,
+    entries: &[(Pubkey, u64)],
+    extra_accounts: Vec<AccountMeta>,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                token_pool,
+                required_signers,
+                ..
+            },
+    } = test_context;
+
+    let mut packed_entries: [(Pubkey, u64); crate::instruction::MAX_BATCH_SIZE] =
+        Default::default();
+    packed_entries[..entries.len()].copy_from_slice(entries);
+
+    let data = VestingInstruction::CreateVestingAccountsBatch {
+        entries_count: entries.len() as u8,
+        entries: packed_entries,
+    }
+    .pack();
+    let mut accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(vesting_type.pubkey(), false),
+        AccountMeta::new_readonly(token_pool.pubkey(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
+    ];
+    accounts.extend(extra_accounts);
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+#[tokio::test]
+async fn test_successful_create_vesting_accounts_batch() {
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(&mut test_context, 500).await;
+
+    call_create_vesting_type(
+        &mut test_context,
+        &construct_default_vesting_schedule(),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let entries = [(test_context.keypairs.token_account.pubkey(), 60u64)];
+    call_create_vesting_accounts_batch(
+        &mut test_context,
+        &entries,
+        vec![
+            AccountMeta::new(test_context.keypairs.vesting.pubkey(), false),
+            AccountMeta::new_readonly(test_context.keypairs.token_account.pubkey(), false),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let TestContext {
+        mut banks_client,
+        keypairs: KeyPairs { vesting_type, .. },
+        ..
+    } = test_context;
+
+    let vesting_type_data =
+        deserialize_account::<VestingTypeAccount>(&mut banks_client, vesting_type.pubkey()).await;
+    assert_eq!(vesting_type_data.locked_tokens_amount, 60);
+}
+
+#[tokio::test]
+async fn test_create_vesting_accounts_batch_if_not_administrator() {
+    let signer_a = Keypair::new();
+    let signer_b = Keypair::new();
+
+    let add_accounts = |program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs| {
+        default_add_accounts(program_test, program_id, keypairs);
+
+        let mut require_signers: [Pubkey; MAX_SIGNERS] = Default::default();
+        require_signers[0] = signer_a.pubkey();
+        require_signers[1] = signer_b.pubkey();
+        let mut required_signers = RequiredSigners {
+            is_initialized: true,
+            require_signers,
+            require_number: 2,
+            all_number: 2,
+            vesting_type_account: keypairs.vesting_type.pubkey(),
+            ..Default::default()
+        };
+        required_signers.set_version(RequiredSigners::VERSION);
+        let data = required_signers.try_to_vec().unwrap();
+        program_test.add_account(
+            keypairs.required_signers.pubkey(),
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                owner: program_id,
+                data,
+                ..Account::default()
+            },
+        );
+    };
+
+    let mut test_context = TestContext::new(add_accounts).await;
+    init_token_accounts(&mut test_context, 500).await;
+
+    call_create_vesting_type(
+        &mut test_context,
+        &construct_default_vesting_schedule(),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let entries = [(test_context.keypairs.token_account.pubkey(), 60u64)];
+    let result = call_create_vesting_accounts_batch(
+        &mut test_context,
+        &entries,
+        vec![
+            AccountMeta::new(test_context.keypairs.vesting.pubkey(), false),
+            AccountMeta::new_readonly(test_context.keypairs.token_account.pubkey(), false),
+        ],
+    )
+    .await;
+
+    ErrorChecker::from(result).check(InstructionError::Custom(12));
+}
+
 #[tokio::test]
 async fn test_successful_create_vesting_account() {
     let mut test_context = TestContext::new(default_add_accounts).await;
@@ -325,11 +476,18 @@ async fn test_successful_create_vesting_account() {
     assert_eq!(
         vesting_data,
         VestingAccount {
+            version: VestingAccount::VERSION,
             is_initialized: true,
             total_tokens: 100,
             withdrawn_tokens: 0,
             token_account: token_account.pubkey(),
             vesting_type_account: vesting_type.pubkey(),
+            revoked: false,
+            realizor: None,
+            whitelisted_tokens: 0,
+            window_start: 0,
+            withdrawn_in_window: 0,
+            last_withdraw_ts: 0,
         }
     );
 
@@ -420,10 +578,52 @@ async fn test_create_vesting_with_non_initialized_vesting_type() {
     ErrorChecker::from(result).check(InstructionError::UninitializedAccount);
 }
 
-// TODO need two signers to check
-// #[tokio::test]
-// async fn test_create_vesting_if_not_administrator() {
-// }
+#[tokio::test]
+async fn test_create_vesting_if_not_administrator() {
+    let signer_a = Keypair::new();
+    let signer_b = Keypair::new();
+
+    let add_accounts = |program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs| {
+        default_add_accounts(program_test, program_id, keypairs);
+
+        let mut require_signers: [Pubkey; MAX_SIGNERS] = Default::default();
+        require_signers[0] = signer_a.pubkey();
+        require_signers[1] = signer_b.pubkey();
+        let mut required_signers = RequiredSigners {
+            is_initialized: true,
+            require_signers,
+            require_number: 2,
+            all_number: 2,
+            vesting_type_account: keypairs.vesting_type.pubkey(),
+            ..Default::default()
+        };
+        required_signers.set_version(RequiredSigners::VERSION);
+        let data = required_signers.try_to_vec().unwrap();
+        program_test.add_account(
+            keypairs.required_signers.pubkey(),
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                owner: program_id,
+                data,
+                ..Account::default()
+            },
+        );
+    };
+
+    let mut test_context = TestContext::new(add_accounts).await;
+    init_token_accounts(&mut test_context, 500).await;
+
+    call_create_vesting_type(
+        &mut test_context,
+        &construct_default_vesting_schedule(),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let result = call_create_vesting(&mut test_context, 100, vec![]).await;
+    ErrorChecker::from(result).check(InstructionError::Custom(12));
+}
 
 #[tokio::test]
 async fn test_create_vesting_account_with_invalid_pool_account() {
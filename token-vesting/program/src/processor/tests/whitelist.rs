@@ -0,0 +1,776 @@
+use chrono::Utc;
+
+use solana_program::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+};
+use solana_program_test::ProgramTest;
+use solana_sdk::{
+    signature::Keypair, signature::Signer, system_instruction, transaction::Transaction,
+    transport::TransportError,
+};
+use spl_token::{
+    self,
+    instruction::{initialize_account, initialize_mint, mint_to},
+};
+
+use crate::instruction::VestingInstruction;
+use crate::state::{
+    LinearVesting, RequiredSigners, TimeBasis, VestingAccount, VestingSchedule, VestingTypeAccount,
+    Whitelist, WithdrawalLog,
+};
+
+use super::common::{
+    add_account, deserialize_account, deserialize_token_account, AbstractTestContext, ErrorChecker,
+};
+
+struct KeyPairs {
+    mint: Keypair,
+    vesting_type: Keypair,
+    vesting: Keypair,
+    token_account: Keypair,
+    token_pool: Keypair,
+    destination_token_account: Keypair,
+    whitelist: Keypair,
+    required_signers: Keypair,
+    withdrawal_log: Keypair,
+    excess_destination: Keypair,
+}
+
+impl Default for KeyPairs {
+    fn default() -> Self {
+        Self {
+            mint: Keypair::new(),
+            vesting_type: Keypair::new(),
+            vesting: Keypair::new(),
+            token_account: Keypair::new(),
+            token_pool: Keypair::new(),
+            destination_token_account: Keypair::new(),
+            whitelist: Keypair::new(),
+            required_signers: Keypair::new(),
+            withdrawal_log: Keypair::new(),
+            excess_destination: Keypair::new(),
+        }
+    }
+}
+
+type TestContext = AbstractTestContext<KeyPairs>;
+
+fn default_add_accounts(program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs) {
+    add_account::<VestingTypeAccount>(program_test, program_id, &keypairs.vesting_type, true);
+    add_account::<VestingAccount>(program_test, program_id, &keypairs.vesting, true);
+    add_account::<Whitelist>(program_test, program_id, &keypairs.whitelist, true);
+    add_account::<RequiredSigners>(program_test, program_id, &keypairs.required_signers, true);
+    add_account::<WithdrawalLog>(program_test, program_id, &keypairs.withdrawal_log, true);
+}
+
+fn mint_init_transaction(
+    payer: &Keypair,
+    mint: &Keypair,
+    mint_authority: &Keypair,
+    recent_blockhash: Hash,
+) -> Transaction {
+    let instructions = [
+        system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            Rent::default().minimum_balance(82),
+            82,
+            &spl_token::id(),
+        ),
+        initialize_mint(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &mint_authority.pubkey(),
+            None,
+            0,
+        )
+        .unwrap(),
+    ];
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer, mint], recent_blockhash);
+    transaction
+}
+
+fn create_token_account(
+    payer: &Keypair,
+    mint: &Keypair,
+    recent_blockhash: Hash,
+    token_account: &Keypair,
+    token_account_owner: &Pubkey,
+) -> Transaction {
+    let instructions = [
+        system_instruction::create_account(
+            &payer.pubkey(),
+            &token_account.pubkey(),
+            Rent::default().minimum_balance(165),
+            165,
+            &spl_token::id(),
+        ),
+        initialize_account(
+            &spl_token::id(),
+            &token_account.pubkey(),
+            &mint.pubkey(),
+            token_account_owner,
+        )
+        .unwrap(),
+    ];
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer, token_account], recent_blockhash);
+    transaction
+}
+
+pub fn mint_to_token_account(
+    payer: &Keypair,
+    mint: &Keypair,
+    to: &Pubkey,
+    recent_blockhash: Hash,
+    amount: u64,
+) -> Transaction {
+    let instructions = [mint_to(
+        &spl_token::id(),
+        &mint.pubkey(),
+        to,
+        &payer.pubkey(),
+        &[&payer.pubkey()],
+        amount,
+    )
+    .unwrap()];
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer], recent_blockhash);
+    transaction
+}
+
+async fn init_token_accounts(
+    test_context: &mut TestContext,
+    tokens_in_pool: u64,
+    beneficiary: &Pubkey,
+    whitelisted_program: &Pubkey,
+) {
+    let TestContext {
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                token_account,
+                token_pool,
+                destination_token_account,
+                excess_destination,
+                mint,
+                ..
+            },
+        ..
+    } = test_context;
+
+    banks_client
+        .process_transaction(mint_init_transaction(
+            &payer,
+            &mint,
+            &payer,
+            recent_blockhash.clone(),
+        ))
+        .await
+        .unwrap();
+
+    banks_client
+        .process_transaction(create_token_account(
+            &payer,
+            &mint,
+            recent_blockhash.clone(),
+            &token_pool,
+            &payer.pubkey(),
+        ))
+        .await
+        .unwrap();
+
+    banks_client
+        .process_transaction(mint_to_token_account(
+            &payer,
+            &mint,
+            &token_pool.pubkey(),
+            recent_blockhash.clone(),
+            tokens_in_pool,
+        ))
+        .await
+        .unwrap();
+
+    banks_client
+        .process_transaction(create_token_account(
+            &payer,
+            &mint,
+            recent_blockhash.clone(),
+            &token_account,
+            beneficiary,
+        ))
+        .await
+        .unwrap();
+
+    banks_client
+        .process_transaction(create_token_account(
+            &payer,
+            &mint,
+            recent_blockhash.clone(),
+            &destination_token_account,
+            whitelisted_program,
+        ))
+        .await
+        .unwrap();
+
+    banks_client
+        .process_transaction(create_token_account(
+            &payer,
+            &mint,
+            recent_blockhash.clone(),
+            &excess_destination,
+            &payer.pubkey(),
+        ))
+        .await
+        .unwrap();
+}
+
+fn construct_default_vesting_schedule(tokens: u64) -> VestingSchedule {
+    let dt = Utc::now();
+    let timestamp = dt.timestamp() as u64;
+    VestingSchedule::with_tokens(tokens)
+        .legacy(
+            timestamp - 200,
+            timestamp + 10,
+            100,
+            timestamp - 110,
+            0,
+            None,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+async fn call_create_vesting_type(
+    test_context: &mut TestContext,
+    vesting_schedule: &VestingSchedule,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                token_pool,
+                ..
+            },
+    } = test_context;
+
+    let mut vestings: [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS] = Default::default();
+    vestings[..vesting_schedule.vestings().len()].copy_from_slice(vesting_schedule.vestings());
+
+    let data = VestingInstruction::CreateVestingType {
+        token_count: vesting_schedule.token_count(),
+        vesting_count: vesting_schedule.vestings().len() as u8,
+        vestings,
+        date_oracle: None,
+        revocable: false,
+        time_basis: TimeBasis::Timestamp,
+        withdrawal_timelock: 0,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(vesting_type.pubkey(), false),
+        AccountMeta::new(token_pool.pubkey(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+async fn call_create_vesting(
+    test_context: &mut TestContext,
+    total_tokens: u64,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                vesting,
+                token_account,
+                token_pool,
+                required_signers,
+                ..
+            },
+    } = test_context;
+
+    let data = VestingInstruction::CreateVestingAccount {
+        total_tokens,
+        realizor: None,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(vesting_type.pubkey(), false),
+        AccountMeta::new(vesting.pubkey(), false),
+        AccountMeta::new_readonly(token_account.pubkey(), false),
+        AccountMeta::new(token_pool.pubkey(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
+    ];
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+async fn call_add_to_whitelist(
+    test_context: &mut TestContext,
+    program: Pubkey,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                whitelist,
+                required_signers,
+                ..
+            },
+    } = test_context;
+
+    let data = VestingInstruction::AddToWhitelist { program }.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(payer.pubkey(), true),
+        AccountMeta::new_readonly(vesting_type.pubkey(), false),
+        AccountMeta::new(whitelist.pubkey(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
+    ];
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+async fn call_remove_from_whitelist(
+    test_context: &mut TestContext,
+    program: Pubkey,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                whitelist,
+                required_signers,
+                ..
+            },
+    } = test_context;
+
+    let data = VestingInstruction::RemoveFromWhitelist { program }.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(payer.pubkey(), true),
+        AccountMeta::new_readonly(vesting_type.pubkey(), false),
+        AccountMeta::new(whitelist.pubkey(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
+    ];
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+async fn call_whitelist_withdraw(
+    test_context: &mut TestContext,
+    signer: &Keypair,
+    amount: u64,
+    whitelisted_program: Pubkey,
+    account_overrides: Vec<(usize, AccountMeta)>,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                vesting,
+                token_pool,
+                token_account,
+                destination_token_account,
+                whitelist,
+                required_signers,
+                ..
+            },
+    } = test_context;
+
+    let (pda, _bump_seed) =
+        Pubkey::find_program_address(&[vesting_type.pubkey().as_ref()], program_id);
+
+    let data = VestingInstruction::WhitelistWithdraw { amount }.pack();
+    let mut accounts = vec![
+        AccountMeta::new_readonly(signer.pubkey(), true),
+        AccountMeta::new_readonly(vesting_type.pubkey(), false),
+        AccountMeta::new(vesting.pubkey(), false),
+        AccountMeta::new(token_pool.pubkey(), false),
+        AccountMeta::new_readonly(token_account.pubkey(), false),
+        AccountMeta::new(destination_token_account.pubkey(), false),
+        AccountMeta::new_readonly(whitelisted_program, false),
+        AccountMeta::new_readonly(whitelist.pubkey(), false),
+        AccountMeta::new_readonly(pda, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
+    ];
+    for (index, account_info) in account_overrides.into_iter() {
+        accounts[index] = account_info;
+    }
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair, signer], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+async fn call_whitelist_deposit(
+    test_context: &mut TestContext,
+    authority: &Keypair,
+    amount: u64,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                vesting,
+                token_pool,
+                destination_token_account,
+                ..
+            },
+    } = test_context;
+
+    let data = VestingInstruction::WhitelistDeposit { amount }.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(authority.pubkey(), true),
+        AccountMeta::new_readonly(vesting_type.pubkey(), false),
+        AccountMeta::new(vesting.pubkey(), false),
+        AccountMeta::new(destination_token_account.pubkey(), false),
+        AccountMeta::new(token_pool.pubkey(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair, authority], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+async fn call_withdraw_excessive_from_pool(
+    test_context: &mut TestContext,
+    amount: u64,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                token_pool,
+                required_signers,
+                withdrawal_log,
+                excess_destination,
+                ..
+            },
+    } = test_context;
+
+    let (pda, _bump_seed) =
+        Pubkey::find_program_address(&[vesting_type.pubkey().as_ref()], program_id);
+
+    let data = VestingInstruction::WithdrawExcessiveFromPool { amount }.pack();
+    let accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(excess_destination.pubkey(), false),
+        AccountMeta::new_readonly(pda, false),
+        AccountMeta::new(token_pool.pubkey(), false),
+        AccountMeta::new_readonly(vesting_type.pubkey(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
+        AccountMeta::new(withdrawal_log.pubkey(), false),
+    ];
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+#[tokio::test]
+async fn test_withdraw_excessive_from_pool_accounts_for_outstanding_whitelisted_tokens() {
+    let beneficiary = Keypair::new();
+    let whitelisted_program = Keypair::new();
+
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(
+        &mut test_context,
+        500,
+        &beneficiary.pubkey(),
+        &whitelisted_program.pubkey(),
+    )
+    .await;
+
+    let tokens = 100;
+    call_create_vesting_type(&mut test_context, &construct_default_vesting_schedule(tokens))
+        .await
+        .unwrap();
+    call_create_vesting(&mut test_context, tokens).await.unwrap();
+
+    call_add_to_whitelist(&mut test_context, whitelisted_program.pubkey())
+        .await
+        .unwrap();
+    call_whitelist_withdraw(
+        &mut test_context,
+        &beneficiary,
+        60,
+        whitelisted_program.pubkey(),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    // Pool now holds 500 - 60 = 440. A stale calculation that only
+    // subtracts `locked_tokens_amount` (100) would allow withdrawing up to
+    // 340, even though 60 of the 100 locked tokens are only out of the pool
+    // because they're staked via whitelist, not because they were ever
+    // released — the genuine excess is 440 - 100 - 60 = 280.
+    let result = call_withdraw_excessive_from_pool(&mut test_context, 300).await;
+    ErrorChecker::from(result).check(InstructionError::Custom(6));
+
+    call_withdraw_excessive_from_pool(&mut test_context, 280)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_successful_whitelist_withdraw_and_deposit() {
+    let beneficiary = Keypair::new();
+    let whitelisted_program = Keypair::new();
+
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(
+        &mut test_context,
+        500,
+        &beneficiary.pubkey(),
+        &whitelisted_program.pubkey(),
+    )
+    .await;
+
+    let tokens = 100;
+    call_create_vesting_type(&mut test_context, &construct_default_vesting_schedule(tokens))
+        .await
+        .unwrap();
+    call_create_vesting(&mut test_context, tokens).await.unwrap();
+
+    call_add_to_whitelist(&mut test_context, whitelisted_program.pubkey())
+        .await
+        .unwrap();
+
+    call_whitelist_withdraw(
+        &mut test_context,
+        &beneficiary,
+        60,
+        whitelisted_program.pubkey(),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let vesting = test_context.keypairs.vesting.pubkey();
+    let destination_token_account = test_context.keypairs.destination_token_account.pubkey();
+
+    let vesting_data = deserialize_account::<VestingAccount>(
+        &mut test_context.banks_client,
+        vesting,
+    )
+    .await;
+    assert_eq!(vesting_data.whitelisted_tokens, 60);
+
+    let destination_data =
+        deserialize_token_account(&mut test_context.banks_client, destination_token_account).await;
+    assert_eq!(destination_data.amount, 60);
+
+    let vesting_type = test_context.keypairs.vesting_type.pubkey();
+    let vesting_type_data =
+        deserialize_account::<VestingTypeAccount>(&mut test_context.banks_client, vesting_type)
+            .await;
+    assert_eq!(vesting_type_data.whitelisted_tokens_amount, 60);
+
+    call_whitelist_deposit(&mut test_context, &whitelisted_program, 60)
+        .await
+        .unwrap();
+
+    let TestContext {
+        mut banks_client, ..
+    } = test_context;
+
+    let vesting_data = deserialize_account::<VestingAccount>(&mut banks_client, vesting).await;
+    assert_eq!(vesting_data.whitelisted_tokens, 0);
+
+    let destination_data = deserialize_token_account(&mut banks_client, destination_token_account).await;
+    assert_eq!(destination_data.amount, 0);
+
+    let vesting_type_data =
+        deserialize_account::<VestingTypeAccount>(&mut banks_client, vesting_type).await;
+    assert_eq!(vesting_type_data.whitelisted_tokens_amount, 0);
+}
+
+#[tokio::test]
+async fn test_whitelist_withdraw_when_not_whitelisted() {
+    let beneficiary = Keypair::new();
+    let whitelisted_program = Keypair::new();
+
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(
+        &mut test_context,
+        500,
+        &beneficiary.pubkey(),
+        &whitelisted_program.pubkey(),
+    )
+    .await;
+
+    let tokens = 100;
+    call_create_vesting_type(&mut test_context, &construct_default_vesting_schedule(tokens))
+        .await
+        .unwrap();
+    call_create_vesting(&mut test_context, tokens).await.unwrap();
+
+    let result = call_whitelist_withdraw(
+        &mut test_context,
+        &beneficiary,
+        60,
+        whitelisted_program.pubkey(),
+        vec![],
+    )
+    .await;
+    ErrorChecker::from(result).check(InstructionError::Custom(16));
+}
+
+#[tokio::test]
+async fn test_whitelist_withdraw_exceeding_total_tokens() {
+    let beneficiary = Keypair::new();
+    let whitelisted_program = Keypair::new();
+
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(
+        &mut test_context,
+        500,
+        &beneficiary.pubkey(),
+        &whitelisted_program.pubkey(),
+    )
+    .await;
+
+    let tokens = 100;
+    call_create_vesting_type(&mut test_context, &construct_default_vesting_schedule(tokens))
+        .await
+        .unwrap();
+    call_create_vesting(&mut test_context, tokens).await.unwrap();
+
+    call_add_to_whitelist(&mut test_context, whitelisted_program.pubkey())
+        .await
+        .unwrap();
+
+    let result = call_whitelist_withdraw(
+        &mut test_context,
+        &beneficiary,
+        tokens + 1,
+        whitelisted_program.pubkey(),
+        vec![],
+    )
+    .await;
+    ErrorChecker::from(result).check(InstructionError::Custom(18));
+}
+
+#[tokio::test]
+async fn test_remove_from_whitelist() {
+    let beneficiary = Keypair::new();
+    let whitelisted_program = Keypair::new();
+
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(
+        &mut test_context,
+        500,
+        &beneficiary.pubkey(),
+        &whitelisted_program.pubkey(),
+    )
+    .await;
+
+    let tokens = 100;
+    call_create_vesting_type(&mut test_context, &construct_default_vesting_schedule(tokens))
+        .await
+        .unwrap();
+    call_create_vesting(&mut test_context, tokens).await.unwrap();
+
+    call_add_to_whitelist(&mut test_context, whitelisted_program.pubkey())
+        .await
+        .unwrap();
+    call_remove_from_whitelist(&mut test_context, whitelisted_program.pubkey())
+        .await
+        .unwrap();
+
+    let result = call_whitelist_withdraw(
+        &mut test_context,
+        &beneficiary,
+        60,
+        whitelisted_program.pubkey(),
+        vec![],
+    )
+    .await;
+    ErrorChecker::from(result).check(InstructionError::Custom(16));
+}
@@ -1,6 +1,6 @@
 use crate::instruction::VestingInstruction;
-use crate::state::{LinearVesting, VestingTypeAccount, MAX_VESTINGS};
-use crate::state::{VestingAccount, VestingSchedule};
+use crate::state::{LinearVesting, RequiredSigners, TimeBasis, VestingTypeAccount, MAX_VESTINGS};
+use crate::state::{VestingAccount, VestingSchedule, WithdrawalLog};
 
 use chrono::Utc;
 use solana_program::instruction::InstructionError;
@@ -30,6 +30,8 @@ type TestContext = AbstractTestContext<KeyPairs>;
 fn default_add_accounts(program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs) {
     add_account::<VestingTypeAccount>(program_test, program_id, &keypairs.vesting_type, true);
     add_account::<VestingAccount>(program_test, program_id, &keypairs.vesting, true);
+    add_account::<RequiredSigners>(program_test, program_id, &keypairs.required_signers, true);
+    add_account::<WithdrawalLog>(program_test, program_id, &keypairs.withdrawal_log, true);
 }
 
 struct KeyPairs {
@@ -40,6 +42,8 @@ struct KeyPairs {
     token_pool: Keypair,
     receiver: Keypair,
     no_admin: Keypair,
+    required_signers: Keypair,
+    withdrawal_log: Keypair,
 }
 
 impl Default for KeyPairs {
@@ -52,53 +56,93 @@ impl Default for KeyPairs {
             token_pool: Keypair::new(),
             receiver: Keypair::new(),
             no_admin: Keypair::new(),
+            required_signers: Keypair::new(),
+            withdrawal_log: Keypair::new(),
         }
     }
 }
 
 #[tokio::test]
 async fn test() {
-    withdraw_excessive_from_pool(100, 10, 0, true)
+    withdraw_excessive_from_pool(100, 10, 0, true, None)
         .await
         .unwrap();
-    withdraw_excessive_from_pool(10000, 5, 10, true)
+    withdraw_excessive_from_pool(10000, 5, 10, true, None)
         .await
         .unwrap();
-    withdraw_excessive_from_pool(800, 700, 50, true)
+    withdraw_excessive_from_pool(800, 700, 50, true, None)
         .await
         .unwrap();
-    withdraw_excessive_from_pool(1000, 1000, 0, true)
+    withdraw_excessive_from_pool(1000, 1000, 0, true, None)
         .await
         .unwrap();
-    withdraw_excessive_from_pool(19, 10, 9, true).await.unwrap();
-    withdraw_excessive_from_pool(300, 10, 0, true)
+    withdraw_excessive_from_pool(19, 10, 9, true, None)
+        .await
+        .unwrap();
+    withdraw_excessive_from_pool(300, 10, 0, true, None)
         .await
         .unwrap();
 }
 
 #[tokio::test]
 async fn test_transfer_more_than_exist() {
-    let result = withdraw_excessive_from_pool(10, 100, 0, true).await;
+    let result = withdraw_excessive_from_pool(10, 100, 0, true, None).await;
     ErrorChecker::from(result).check(InstructionError::Custom(6));
 }
 
 #[tokio::test]
 async fn test_transfer_without_administrator() {
-    let result = withdraw_excessive_from_pool(100, 10, 0, false).await;
+    let result = withdraw_excessive_from_pool(100, 10, 0, false, None).await;
     ErrorChecker::from(result).check(InstructionError::Custom(4));
 }
 
 #[tokio::test]
 async fn test_transfer_more_than_unlocked() {
-    let result = withdraw_excessive_from_pool(100, 11, 90, true).await;
+    let result = withdraw_excessive_from_pool(100, 11, 90, true, None).await;
     ErrorChecker::from(result).check(InstructionError::Custom(6));
 }
 
+#[tokio::test]
+async fn test_transfer_rejects_aliased_token_pool_and_associated_account() {
+    let result = withdraw_excessive_from_pool(
+        100,
+        10,
+        0,
+        true,
+        Some(AliasedAccount::TokenPoolAsAssociatedAccount),
+    )
+    .await;
+    ErrorChecker::from(result).check(InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn test_transfer_rejects_aliased_vesting_type_and_pda() {
+    let result = withdraw_excessive_from_pool(
+        100,
+        10,
+        0,
+        true,
+        Some(AliasedAccount::VestingTypeAsPda),
+    )
+    .await;
+    ErrorChecker::from(result).check(InstructionError::InvalidAccountData);
+}
+
+/// Which pair of distinct-account slots a test wants to alias to the same
+/// `Keypair`, to exercise `assert_distinct`'s rejection of each required-
+/// distinct pair in `withdraw_excessive_from_pool`'s `Accounts::try_from`.
+#[derive(Clone, Copy)]
+enum AliasedAccount {
+    TokenPoolAsAssociatedAccount,
+    VestingTypeAsPda,
+}
+
 async fn withdraw_excessive_from_pool(
     token_pool_amount: u64,
     amount_to_transfer: u64,
     locked_tokens_amount: u64,
     administrator: bool,
+    alias: Option<AliasedAccount>,
 ) -> Result<(), TransportError> {
     let mut test_context = TestContext::new(default_add_accounts).await;
 
@@ -143,7 +187,7 @@ async fn withdraw_excessive_from_pool(
         .unwrap();
 
     //transfer
-    call_transfer(&mut test_context, amount_to_transfer, administrator).await?;
+    call_transfer(&mut test_context, amount_to_transfer, administrator, alias).await?;
 
     //check administrator amount
     let admin = test_context.payer.pubkey().clone();
@@ -237,16 +281,17 @@ pub fn create_vesting_instruction(
     vesting: &Pubkey,
     receiver: &Pubkey,
     token_pool: &Pubkey,
+    required_signers: &Pubkey,
     total_tokens: u64,
 ) -> Instruction {
-    let data = VestingInstruction::CreateVestingAccount { total_tokens }.pack();
+    let data = VestingInstruction::CreateVestingAccount { total_tokens, realizor: None }.pack();
     let accounts = vec![
         AccountMeta::new(*signer, true),
         AccountMeta::new(*vesting_type, false),
         AccountMeta::new(*vesting, false),
         AccountMeta::new_readonly(*receiver, false),
         AccountMeta::new_readonly(*token_pool, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(*required_signers, false),
     ];
     Instruction {
         program_id: *vesting_program_id,
@@ -262,6 +307,8 @@ pub fn withdraw_excessive_from_pool_instruction(
     pda: &Pubkey,
     token_pool: &Pubkey,
     vesting_type: &Pubkey,
+    required_signers: &Pubkey,
+    withdrawal_log: &Pubkey,
     amount: u64,
 ) -> Instruction {
     let data = VestingInstruction::WithdrawExcessiveFromPool { amount }.pack();
@@ -272,6 +319,8 @@ pub fn withdraw_excessive_from_pool_instruction(
         AccountMeta::new(*token_pool, false),
         AccountMeta::new_readonly(*vesting_type, false),
         AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(*required_signers, false),
+        AccountMeta::new(*withdrawal_log, false),
     ];
     Instruction {
         program_id: *vesting_program_id,
@@ -289,6 +338,8 @@ fn withdraw_excessive_from_pool_transaction(
     pda: &Pubkey,
     vesting_type: &Keypair,
     token_pool: &Keypair,
+    required_signers: &Pubkey,
+    withdrawal_log: &Pubkey,
     recent_blockhash: Hash,
     amount: u64,
 ) -> Transaction {
@@ -299,6 +350,8 @@ fn withdraw_excessive_from_pool_transaction(
         &pda,
         &token_pool.pubkey(),
         &vesting_type.pubkey(),
+        required_signers,
+        withdrawal_log,
         amount,
     )];
     let mut transaction = Transaction::new_with_payer(&init_instruction, Some(&payer.pubkey()));
@@ -313,6 +366,7 @@ fn create_vesting_transaction(
     vesting: &Keypair,
     receiver: &Pubkey,
     token_pool: &Keypair,
+    required_signers: &Pubkey,
     recent_blockhash: Hash,
     total_tokens: u64,
 ) -> Transaction {
@@ -323,6 +377,7 @@ fn create_vesting_transaction(
         &vesting.pubkey(),
         &receiver,
         &token_pool.pubkey(),
+        required_signers,
         total_tokens,
     );
     let mut transaction = Transaction::new_with_payer(&[init_instruction], Some(&payer.pubkey()));
@@ -452,6 +507,10 @@ async fn call_create_vesting_type(
         token_count: vesting_schedule.token_count(),
         vesting_count: vesting_schedule.vestings().len() as u8,
         vestings,
+        date_oracle: None,
+        revocable: false,
+        time_basis: TimeBasis::Timestamp,
+        withdrawal_timelock: 0,
     }
     .pack();
     let accounts = accounts.unwrap_or(vec![
@@ -507,6 +566,7 @@ async fn call_create_vesting_account(
                 token_pool,
                 receiver,
                 mint,
+                required_signers,
                 ..
             },
         program_id,
@@ -519,6 +579,7 @@ async fn call_create_vesting_account(
             &vesting,
             &get_associated_token_address(&receiver.pubkey(), &mint.pubkey()),
             &token_pool,
+            &required_signers.pubkey(),
             *recent_blockhash,
             locked_tokens_amount,
         ))
@@ -529,6 +590,7 @@ async fn call_transfer(
     test_context: &mut TestContext,
     amount_to_transfer: u64,
     administrator: bool,
+    alias: Option<AliasedAccount>,
 ) -> Result<(), TransportError> {
     let TestContext {
         banks_client,
@@ -540,25 +602,37 @@ async fn call_transfer(
                 vesting_type,
                 token_pool,
                 no_admin,
+                required_signers,
+                withdrawal_log,
                 ..
             },
         program_id,
     } = test_context;
     let (pda, _bump_seed) =
         Pubkey::find_program_address(&[vesting_type.pubkey().as_ref()], &program_id);
+    let associated_account = if let Some(AliasedAccount::TokenPoolAsAssociatedAccount) = alias {
+        token_pool.pubkey()
+    } else if administrator {
+        get_associated_token_address(&payer.pubkey(), &mint.pubkey())
+    } else {
+        get_associated_token_address(&no_admin.pubkey(), &mint.pubkey())
+    };
+    let pda = if let Some(AliasedAccount::VestingTypeAsPda) = alias {
+        vesting_type.pubkey()
+    } else {
+        pda
+    };
     banks_client
         .process_transaction(withdraw_excessive_from_pool_transaction(
             *program_id,
             &payer,
             if administrator { &payer } else { &no_admin },
-            &if administrator {
-                get_associated_token_address(&payer.pubkey(), &mint.pubkey())
-            } else {
-                get_associated_token_address(&no_admin.pubkey(), &mint.pubkey())
-            },
+            &associated_account,
             &pda,
             &vesting_type,
             &token_pool,
+            &required_signers.pubkey(),
+            &withdrawal_log.pubkey(),
             *recent_blockhash,
             amount_to_transfer,
         ))
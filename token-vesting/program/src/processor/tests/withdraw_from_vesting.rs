@@ -2,7 +2,7 @@ use chrono::Utc;
 
 use solana_program::{
     hash::Hash,
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, InstructionError},
     pubkey::Pubkey,
     rent::Rent,
 };
@@ -17,10 +17,13 @@ use spl_token::{
 };
 
 use crate::instruction::VestingInstruction;
-use crate::state::{LinearVesting, VestingAccount, VestingSchedule, VestingTypeAccount};
+use crate::state::{
+    LinearVesting, RequiredSigners, TimeBasis, VestingAccount, VestingSchedule, VestingTypeAccount,
+    WithdrawalLog,
+};
 
 use super::common::{
-    add_account, deserialize_account, deserialize_token_account, AbstractTestContext,
+    add_account, deserialize_account, deserialize_token_account, AbstractTestContext, ErrorChecker,
 };
 
 struct KeyPairs {
@@ -29,6 +32,8 @@ struct KeyPairs {
     vesting: Keypair,
     token_account: Keypair,
     token_pool: Keypair,
+    required_signers: Keypair,
+    withdrawal_log: Keypair,
 }
 
 impl Default for KeyPairs {
@@ -39,6 +44,8 @@ impl Default for KeyPairs {
             vesting: Keypair::new(),
             token_account: Keypair::new(),
             token_pool: Keypair::new(),
+            required_signers: Keypair::new(),
+            withdrawal_log: Keypair::new(),
         }
     }
 }
@@ -48,6 +55,8 @@ type TestContext = AbstractTestContext<KeyPairs>;
 fn default_add_accounts(program_test: &mut ProgramTest, program_id: Pubkey, keypairs: &KeyPairs) {
     add_account::<VestingTypeAccount>(program_test, program_id, &keypairs.vesting_type, true);
     add_account::<VestingAccount>(program_test, program_id, &keypairs.vesting, true);
+    add_account::<RequiredSigners>(program_test, program_id, &keypairs.required_signers, true);
+    add_account::<WithdrawalLog>(program_test, program_id, &keypairs.withdrawal_log, true);
 }
 
 fn mint_init_transaction(
@@ -206,6 +215,7 @@ fn construct_default_vesting_schedule(tokens: u64) -> VestingSchedule {
 async fn call_create_vesting_type(
     test_context: &mut TestContext,
     vesting_schedule: &VestingSchedule,
+    withdrawal_timelock: i64,
     account_overrides: Vec<(usize, AccountMeta)>,
 ) -> Result<(), TransportError> {
     let TestContext {
@@ -228,6 +238,10 @@ async fn call_create_vesting_type(
         token_count: vesting_schedule.token_count(),
         vesting_count: vesting_schedule.vestings().len() as u8,
         vestings,
+        date_oracle: None,
+        revocable: false,
+        time_basis: TimeBasis::Timestamp,
+        withdrawal_timelock,
     }
     .pack();
     let mut accounts = vec![
@@ -266,17 +280,19 @@ async fn call_create_vesting(
                 vesting,
                 token_account,
                 token_pool,
+                required_signers,
                 ..
             },
     } = test_context;
 
-    let data = VestingInstruction::CreateVestingAccount { total_tokens }.pack();
+    let data = VestingInstruction::CreateVestingAccount { total_tokens, realizor: None }.pack();
     let mut accounts = vec![
         AccountMeta::new(payer.pubkey(), true),
         AccountMeta::new(vesting_type.pubkey(), false),
         AccountMeta::new(vesting.pubkey(), false),
         AccountMeta::new_readonly(token_account.pubkey(), false),
         AccountMeta::new(token_pool.pubkey(), false),
+        AccountMeta::new(required_signers.pubkey(), false),
     ];
     for (index, account_info) in account_overrides.into_iter() {
         accounts[index] = account_info;
@@ -308,6 +324,7 @@ async fn call_withdraw_from_vesting(
                 vesting,
                 token_account,
                 token_pool,
+                withdrawal_log,
                 ..
             },
     } = test_context;
@@ -323,6 +340,44 @@ async fn call_withdraw_from_vesting(
         AccountMeta::new(token_pool.pubkey(), false),
         AccountMeta::new(pda, false),             // pda
         AccountMeta::new(spl_token::id(), false), // token program
+        AccountMeta::new(withdrawal_log.pubkey(), false),
+    ];
+    for (index, account_info) in account_overrides.into_iter() {
+        accounts[index] = account_info;
+    }
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.partial_sign(&[payer as &Keypair], recent_blockhash.clone());
+    banks_client.process_transaction(transaction).await
+}
+
+async fn call_init_withdrawal_log(
+    test_context: &mut TestContext,
+    account_overrides: Vec<(usize, AccountMeta)>,
+) -> Result<(), TransportError> {
+    let TestContext {
+        program_id,
+        banks_client,
+        recent_blockhash,
+        payer,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                withdrawal_log,
+                ..
+            },
+    } = test_context;
+
+    let data = VestingInstruction::InitWithdrawalLog.pack();
+    let mut accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new_readonly(vesting_type.pubkey(), false),
+        AccountMeta::new(withdrawal_log.pubkey(), false),
     ];
     for (index, account_info) in account_overrides.into_iter() {
         accounts[index] = account_info;
@@ -348,6 +403,7 @@ async fn test_successful_withdraw_from_vesting() {
     call_create_vesting_type(
         &mut test_context,
         &construct_default_vesting_schedule(tokens),
+        0,
         vec![],
     )
     .await
@@ -384,3 +440,79 @@ async fn test_successful_withdraw_from_vesting() {
         deserialize_token_account(&mut banks_client, token_account.pubkey()).await;
     assert_eq!(token_account_data.amount, 40);
 }
+
+#[tokio::test]
+async fn test_withdraw_from_vesting_records_to_withdrawal_log() {
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(&mut test_context, 500).await;
+
+    let tokens = 100;
+
+    call_create_vesting_type(
+        &mut test_context,
+        &construct_default_vesting_schedule(tokens),
+        0,
+        vec![],
+    )
+    .await
+    .unwrap();
+    call_create_vesting(&mut test_context, tokens, vec![])
+        .await
+        .unwrap();
+    call_init_withdrawal_log(&mut test_context, vec![])
+        .await
+        .unwrap();
+
+    call_withdraw_from_vesting(&mut test_context, 40, vec![])
+        .await
+        .unwrap();
+
+    let TestContext {
+        mut banks_client,
+        keypairs:
+            KeyPairs {
+                vesting_type,
+                token_account,
+                withdrawal_log,
+                ..
+            },
+        ..
+    } = test_context;
+
+    let withdrawal_log_data =
+        deserialize_account::<WithdrawalLog>(&mut banks_client, withdrawal_log.pubkey()).await;
+    assert!(withdrawal_log_data.is_initialized);
+    assert_eq!(withdrawal_log_data.vesting_type_account, vesting_type.pubkey());
+    assert_eq!(withdrawal_log_data.total_recorded, 1);
+    assert_eq!(withdrawal_log_data.total_withdrawn, 40);
+    assert_eq!(withdrawal_log_data.entries[0].amount, 40);
+    assert_eq!(withdrawal_log_data.entries[0].destination, token_account.pubkey());
+    assert_eq!(withdrawal_log_data.entries[0].running_total, 40);
+}
+
+#[tokio::test]
+async fn test_withdraw_from_vesting_respects_withdrawal_timelock() {
+    let mut test_context = TestContext::new(default_add_accounts).await;
+    init_token_accounts(&mut test_context, 500).await;
+
+    let tokens = 100;
+
+    call_create_vesting_type(
+        &mut test_context,
+        &construct_default_vesting_schedule(tokens),
+        100,
+        vec![],
+    )
+    .await
+    .unwrap();
+    call_create_vesting(&mut test_context, tokens, vec![])
+        .await
+        .unwrap();
+
+    call_withdraw_from_vesting(&mut test_context, 10, vec![])
+        .await
+        .unwrap();
+
+    let result = call_withdraw_from_vesting(&mut test_context, 10, vec![]).await;
+    ErrorChecker::from(result).check(InstructionError::Custom(23));
+}
@@ -0,0 +1,45 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::DateOracle,
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn update_date_oracle(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        timestamp: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let authority = next_account_info(account_info_iter)?;
+        let date_oracle = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut date_oracle_data = read_from_storage::<DateOracle>(date_oracle)?;
+
+        if date_oracle_data.is_initialized {
+            if &date_oracle_data.authority != authority.key {
+                return Err(VestingError::NotAdministrator.into());
+            }
+        } else {
+            date_oracle_data.is_initialized = true;
+            date_oracle_data.authority = *authority.key;
+        }
+
+        date_oracle_data.timestamp = timestamp;
+
+        write_to_storage(date_oracle_data, date_oracle)
+    }
+}
@@ -0,0 +1,120 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use spl_token::state::Account as TokenAccount;
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::{VestingAccount, VestingTypeAccount},
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn whitelist_deposit(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let authority = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let vesting = next_account_info(account_info_iter)?;
+        let source_token_account = next_account_info(account_info_iter)?;
+        let token_pool = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
+        let source_token_account_data = TokenAccount::unpack(&source_token_account.data.borrow())?;
+
+        validate_vesting(&vesting_data, vesting_type)?;
+        validate_token_pool(token_pool, &vesting_type_data)?;
+        validate_source_token_account(source_token_account, &source_token_account_data, authority)?;
+        check_enough_whitelisted_tokens(&vesting_data, amount)?;
+
+        let transfer_tokens = spl_token::instruction::transfer(
+            token_program.key,
+            source_token_account.key,
+            token_pool.key,
+            authority.key,
+            &[authority.key],
+            amount,
+        )?;
+
+        invoke(
+            &transfer_tokens,
+            &[
+                token_program.clone(),
+                source_token_account.clone(),
+                token_pool.clone(),
+                authority.clone(),
+            ],
+        )?;
+
+        vesting_data.whitelisted_tokens -= amount;
+        write_to_storage(vesting_data, vesting)?;
+
+        vesting_type_data.whitelisted_tokens_amount -= amount;
+        write_to_storage(vesting_type_data, vesting_type)?;
+
+        Ok(())
+    }
+}
+
+fn validate_vesting(vesting_data: &VestingAccount, vesting_type: &AccountInfo) -> ProgramResult {
+    if !vesting_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if &vesting_data.vesting_type_account != vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+fn validate_token_pool(
+    token_pool: &AccountInfo,
+    vesting_type_data: &VestingTypeAccount,
+) -> ProgramResult {
+    if token_pool.key != &vesting_type_data.token_pool {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+fn validate_source_token_account(
+    source_token_account: &AccountInfo,
+    source_token_account_data: &TokenAccount,
+    authority: &AccountInfo,
+) -> ProgramResult {
+    if source_token_account.owner != &spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if &source_token_account_data.owner != authority.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+fn check_enough_whitelisted_tokens(vesting_data: &VestingAccount, amount: u64) -> ProgramResult {
+    if amount > vesting_data.whitelisted_tokens {
+        return Err(VestingError::NotEnoughWhitelistedTokens.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use spl_token::state::Account as TokenAccount;
+
+use super::Processor;
+use crate::{
+    error::VestingError,
+    state::{RequiredSigners, VestingAccount, VestingTypeAccount, Whitelist},
+    utils::{read_from_storage, write_to_storage},
+};
+
+impl Processor {
+    pub fn whitelist_withdraw(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let signer = next_account_info(account_info_iter)?;
+        let vesting_type = next_account_info(account_info_iter)?;
+        let vesting = next_account_info(account_info_iter)?;
+        let token_pool = next_account_info(account_info_iter)?;
+        let current_token_account = next_account_info(account_info_iter)?;
+        let destination_token_account = next_account_info(account_info_iter)?;
+        let whitelisted_program = next_account_info(account_info_iter)?;
+        let whitelist = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
+        let whitelist_data = read_from_storage::<Whitelist>(whitelist)?;
+        let current_token_account_data = TokenAccount::unpack(&current_token_account.data.borrow())?;
+        let destination_token_account_data =
+            TokenAccount::unpack(&destination_token_account.data.borrow())?;
+
+        validate_vesting(&vesting_data, vesting_type, current_token_account)?;
+        validate_token_pool(token_pool, &vesting_type_data)?;
+        let committee = validate_authority(
+            &vesting_type_data,
+            vesting_type,
+            signer,
+            &current_token_account_data,
+            required_signers_account,
+            instruction_data,
+        )?;
+        validate_whitelist(&whitelist_data, vesting_type, whitelisted_program)?;
+        validate_destination_token_account(
+            destination_token_account,
+            &destination_token_account_data,
+            whitelisted_program,
+        )?;
+        check_enough_tokens(&vesting_data, amount)?;
+
+        let (pda, bump_seed) =
+            Pubkey::find_program_address(&[vesting_type.key.as_ref()], program_id);
+        if pda_account.key != &pda {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let seed = &[vesting_type.key.as_ref(), &[bump_seed]];
+
+        let transfer_tokens = spl_token::instruction::transfer(
+            token_program.key,
+            token_pool.key,
+            destination_token_account.key,
+            &pda,
+            &[&pda],
+            amount,
+        )?;
+
+        invoke_signed(
+            &transfer_tokens,
+            &[
+                token_program.clone(),
+                token_pool.clone(),
+                destination_token_account.clone(),
+                pda_account.clone(),
+            ],
+            &[seed],
+        )?;
+
+        vesting_data.whitelisted_tokens += amount;
+        write_to_storage(vesting_data, vesting)?;
+
+        vesting_type_data.whitelisted_tokens_amount += amount;
+        write_to_storage(vesting_type_data, vesting_type)?;
+
+        if let Some(mut required_signers_data) = committee {
+            required_signers_data.clear_pending_action();
+            write_to_storage(required_signers_data, required_signers_account)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_vesting(
+    vesting_data: &VestingAccount,
+    vesting_type: &AccountInfo,
+    current_token_account: &AccountInfo,
+) -> ProgramResult {
+    if !vesting_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if &vesting_data.vesting_type_account != vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if &vesting_data.token_account != current_token_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+fn validate_token_pool(
+    token_pool: &AccountInfo,
+    vesting_type_data: &VestingTypeAccount,
+) -> ProgramResult {
+    if token_pool.key != &vesting_type_data.token_pool {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+/// Authorizes the call either against the beneficiary of record (the owner
+/// of the Vesting Account's receiver token account, who may always stake
+/// their own still-locked tokens), or, falling back to the same
+/// administrator/committee gating as other privileged actions.
+fn validate_authority(
+    vesting_type_data: &VestingTypeAccount,
+    vesting_type: &AccountInfo,
+    signer: &AccountInfo,
+    current_token_account_data: &TokenAccount,
+    required_signers_account: &AccountInfo,
+    instruction_data: &[u8],
+) -> Result<Option<RequiredSigners>, ProgramError> {
+    if &current_token_account_data.owner == signer.key {
+        return Ok(None);
+    }
+
+    if !vesting_type_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
+    if !required_signers_data.is_initialized {
+        if &vesting_type_data.administrator != signer.key {
+            return Err(VestingError::NotAdministrator.into());
+        }
+
+        return Ok(None);
+    }
+
+    if required_signers_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let action = RequiredSigners::action_hash(vesting_type.key, instruction_data);
+    if !required_signers_data.is_approved(action) {
+        return Err(VestingError::InsufficientApprovals.into());
+    }
+
+    Ok(Some(required_signers_data))
+}
+
+fn validate_whitelist(
+    whitelist_data: &Whitelist,
+    vesting_type: &AccountInfo,
+    whitelisted_program: &AccountInfo,
+) -> ProgramResult {
+    if !whitelist_data.is_initialized || whitelist_data.vesting_type_account != *vesting_type.key {
+        return Err(VestingError::ProgramNotWhitelisted.into());
+    }
+
+    if !whitelist_data.contains(whitelisted_program.key) {
+        return Err(VestingError::ProgramNotWhitelisted.into());
+    }
+
+    Ok(())
+}
+
+fn validate_destination_token_account(
+    destination_token_account: &AccountInfo,
+    destination_token_account_data: &TokenAccount,
+    whitelisted_program: &AccountInfo,
+) -> ProgramResult {
+    if destination_token_account.owner != &spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if &destination_token_account_data.owner != whitelisted_program.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+fn check_enough_tokens(vesting_data: &VestingAccount, amount: u64) -> ProgramResult {
+    if vesting_data.withdrawn_tokens + vesting_data.whitelisted_tokens + amount
+        > vesting_data.total_tokens
+    {
+        return Err(VestingError::WhitelistWithdrawalExceedsTotal.into());
+    }
+
+    Ok(())
+}
@@ -1,17 +1,22 @@
 use std::convert::TryFrom;
 
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     program::invoke_signed,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 use spl_token::state::Account as TokenAccount;
 
-use crate::{error::VestingError, state::VestingTypeAccount};
+use crate::{
+    error::VestingError,
+    state::{RequiredSigners, VestingTypeAccount, WithdrawalEntry, WithdrawalLog},
+    utils::{assert_distinct, read_from_storage, write_to_storage},
+};
 
 use super::Processor;
 #[derive(Clone, Copy)]
@@ -22,6 +27,8 @@ struct Accounts<'a, 'b> {
     token_pool: &'a AccountInfo<'b>,
     vesting_type: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
+    required_signers_account: &'a AccountInfo<'b>,
+    withdrawal_log_account: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> TryFrom<&'a [AccountInfo<'b>]> for Accounts<'a, 'b> {
@@ -36,11 +43,18 @@ impl<'a, 'b> TryFrom<&'a [AccountInfo<'b>]> for Accounts<'a, 'b> {
         let token_pool = next_account_info(account_info_iter)?;
         let vesting_type = next_account_info(account_info_iter)?;
         let token_program = next_account_info(account_info_iter)?;
+        let required_signers_account = next_account_info(account_info_iter)?;
+        let withdrawal_log_account = next_account_info(account_info_iter)?;
 
         if !signer.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        // A self-transfer would let `token_pool_data.amount`, read before the
+        // transfer, desynchronize from the pool's real post-transfer balance.
+        assert_distinct(&[token_pool, associated_account])?;
+        assert_distinct(&[vesting_type, pda_account])?;
+
         Ok(Accounts {
             signer,
             associated_account,
@@ -48,6 +62,8 @@ impl<'a, 'b> TryFrom<&'a [AccountInfo<'b>]> for Accounts<'a, 'b> {
             token_pool,
             vesting_type,
             token_program,
+            required_signers_account,
+            withdrawal_log_account,
         })
     }
 }
@@ -57,6 +73,7 @@ impl Processor {
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         amount: u64,
+        instruction_data: &[u8],
     ) -> ProgramResult {
         let Accounts {
             signer,
@@ -65,18 +82,36 @@ impl Processor {
             token_pool,
             vesting_type,
             token_program,
+            required_signers_account,
+            withdrawal_log_account,
         } = Accounts::try_from(accounts)?;
 
-        let vesting_type_data = VestingTypeAccount::try_from_slice(&vesting_type.data.borrow())?;
+        let vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
         let token_pool_data = TokenAccount::unpack(&token_pool.data.borrow())?;
 
         let total_tokens = token_pool_data.amount;
-        let unlocked_tokens = total_tokens - vesting_type_data.locked_tokens_amount;
+        // Tokens staked out via `WhitelistWithdraw` leave the pool's real SPL
+        // balance without releasing `locked_tokens_amount` (they're still
+        // committed to a beneficiary, just held elsewhere), so both must be
+        // subtracted to find the genuine excess. Saturating, not wrapping:
+        // if the pool is under-collateralized relative to what it owes,
+        // there is no excess, and the `amount > unlocked_tokens` check below
+        // rejects any nonzero `amount` as `NotEnoughUnlockedTokensInPool`.
+        let unlocked_tokens = total_tokens
+            .saturating_sub(vesting_type_data.locked_tokens_amount)
+            .saturating_sub(vesting_type_data.whitelisted_tokens_amount);
 
         let (pda, bump_seed) =
             Pubkey::find_program_address(&[vesting_type.key.as_ref()], program_id);
 
-        validate_vesting_type(&vesting_type_data, vesting_type, signer, program_id)?;
+        let committee = validate_vesting_type(
+            &vesting_type_data,
+            vesting_type,
+            signer,
+            program_id,
+            required_signers_account,
+            instruction_data,
+        )?;
         validate_token_pool(
             token_pool,
             token_pool_data,
@@ -109,16 +144,39 @@ impl Processor {
                 pda_account.clone(),
             ],
             &[seed],
+        )?;
+
+        if let Some(mut required_signers_data) = committee {
+            required_signers_data.clear_pending_action();
+            write_to_storage(required_signers_data, required_signers_account)?;
+        }
+
+        WithdrawalLog::record_if_configured(
+            withdrawal_log_account,
+            vesting_type.key,
+            WithdrawalEntry {
+                slot: Clock::get()?.slot,
+                amount,
+                destination: *associated_account.key,
+                running_total: 0,
+            },
         )
     }
 }
 
+/// Authorizes the call either against the single `administrator`, or, once a
+/// committee has been configured via `CreateMultisig`, against a pending
+/// approval of this exact instruction collected through
+/// `ApprovePrivilegedAction`. Returns the committee's `RequiredSigners` so the
+/// caller can clear the consumed approval once the withdrawal commits.
 fn validate_vesting_type(
     vesting_type_data: &VestingTypeAccount,
     vesting_type: &AccountInfo,
     signer: &AccountInfo,
     program_id: &Pubkey,
-) -> ProgramResult {
+    required_signers_account: &AccountInfo,
+    instruction_data: &[u8],
+) -> Result<Option<RequiredSigners>, ProgramError> {
     if vesting_type.owner != program_id {
         return Err(ProgramError::IncorrectProgramId);
     }
@@ -127,11 +185,25 @@ fn validate_vesting_type(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if &vesting_type_data.administrator != signer.key {
-        return Err(VestingError::NotAdministrator.into());
+    let required_signers_data = read_from_storage::<RequiredSigners>(required_signers_account)?;
+    if !required_signers_data.is_initialized {
+        if &vesting_type_data.administrator != signer.key {
+            return Err(VestingError::NotAdministrator.into());
+        }
+
+        return Ok(None);
     }
 
-    Ok(())
+    if required_signers_data.vesting_type_account != *vesting_type.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let action = RequiredSigners::action_hash(vesting_type.key, instruction_data);
+    if !required_signers_data.is_approved(action) {
+        return Err(VestingError::InsufficientApprovals.into());
+    }
+
+    Ok(Some(required_signers_data))
 }
 
 fn validate_token_pool(
@@ -1,9 +1,9 @@
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     clock::Clock,
     entrypoint::ProgramResult,
-    program::invoke_signed,
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvar::Sysvar,
@@ -11,8 +11,8 @@ use solana_program::{
 
 use crate::{
     error::VestingError,
-    state::{VestingAccount, VestingTypeAccount},
-    utils::write_to_storage,
+    state::{DateOracle, VestingAccount, VestingTypeAccount, WithdrawalEntry, WithdrawalLog},
+    utils::{read_from_storage, write_to_storage},
 };
 
 use super::Processor;
@@ -31,10 +31,10 @@ impl Processor {
         let token_pool = next_account_info(account_info_iter)?;
         let pda_account = next_account_info(account_info_iter)?;
         let token_program = next_account_info(account_info_iter)?;
+        let withdrawal_log_account = next_account_info(account_info_iter)?;
 
-        let mut vesting_type_data =
-            VestingTypeAccount::try_from_slice(&vesting_type.data.borrow())?;
-        let mut vesting_data = VestingAccount::try_from_slice(&vesting.data.borrow())?;
+        let mut vesting_type_data = read_from_storage::<VestingTypeAccount>(vesting_type)?;
+        let mut vesting_data = read_from_storage::<VestingAccount>(vesting)?;
         let (pda, bump_seed) =
             Pubkey::find_program_address(&[vesting_type.key.as_ref()], program_id);
 
@@ -44,7 +44,14 @@ impl Processor {
         validate_token_pool(token_pool, &vesting_type_data)?;
         validate_pda_account(pda_account, &pda)?;
         validate_token_program_account(token_program)?;
-        check_enough_tokens_to_withdraw(&vesting_data, &vesting_type_data, amount)?;
+
+        let now = current_unlock_timestamp(&vesting_type_data, account_info_iter)?;
+        check_enough_tokens_to_withdraw(&vesting_data, &vesting_type_data, amount, now)?;
+        check_realized(&vesting_data, vesting, account_info_iter)?;
+        check_type_realized(&vesting_type_data, vesting, account_info_iter)?;
+
+        let withdrawal_ts = Clock::get()?.unix_timestamp;
+        check_withdrawal_timelock(&vesting_data, &vesting_type_data, withdrawal_ts)?;
 
         let transfer_tokens_ix = spl_token::instruction::transfer(
             token_program.key,
@@ -65,11 +72,24 @@ impl Processor {
             &[&[vesting_type.key.as_ref(), &[bump_seed]]],
         )?;
 
+        vesting_data.record_withdrawal_for_rate_limit(&vesting_type_data.vesting_schedule, amount, now);
         vesting_data.withdrawn_tokens += amount;
+        vesting_data.last_withdraw_ts = withdrawal_ts;
         write_to_storage(vesting_data, vesting)?;
 
         vesting_type_data.locked_tokens_amount -= amount;
-        write_to_storage(vesting_type_data, vesting_type)
+        write_to_storage(vesting_type_data, vesting_type)?;
+
+        WithdrawalLog::record_if_configured(
+            withdrawal_log_account,
+            vesting_type.key,
+            WithdrawalEntry {
+                slot: Clock::get()?.slot,
+                amount,
+                destination: *token_account.key,
+                running_total: 0,
+            },
+        )
     }
 }
 
@@ -135,13 +155,148 @@ fn check_enough_tokens_to_withdraw(
     vesting_data: &VestingAccount,
     vesting_type_data: &VestingTypeAccount,
     amount: u64,
+    now: u64,
 ) -> ProgramResult {
-    let now = Clock::get()?.unix_timestamp as u64;
-    let available_to_withdraw = vesting_data
-        .calculate_available_to_withdraw_amount(&vesting_type_data.vesting_schedule, now);
+    let available_to_withdraw =
+        vesting_data.calculate_withdrawable_with_cap(&vesting_type_data.vesting_schedule, now);
     if available_to_withdraw < amount {
         Err(VestingError::NotEnoughUnlockedTokens.into())
     } else {
         Ok(())
     }
 }
+
+/// Rate-limits claims independently of the vesting schedule itself: rejects
+/// the withdrawal unless at least `withdrawal_timelock` seconds of wall-clock
+/// time have passed since this Vesting Account's last withdrawal. A
+/// Vesting Type with no `withdrawal_timelock` configured (`0`), or a Vesting
+/// Account that has never withdrawn (`last_withdraw_ts == 0`), is unaffected.
+fn check_withdrawal_timelock(
+    vesting_data: &VestingAccount,
+    vesting_type_data: &VestingTypeAccount,
+    now: i64,
+) -> ProgramResult {
+    if vesting_data.last_withdraw_ts == 0 {
+        return Ok(());
+    }
+
+    if now - vesting_data.last_withdraw_ts < vesting_type_data.withdrawal_timelock {
+        return Err(VestingError::WithdrawalTimelocked.into());
+    }
+
+    Ok(())
+}
+
+/// Returns the timestamp used to evaluate the vesting schedule: a published
+/// value read from the trusted `DateOracle` account when the Vesting Type
+/// configures one, or the Clock sysvar otherwise.
+fn current_unlock_timestamp<'a, 'b, I>(
+    vesting_type_data: &VestingTypeAccount,
+    account_info_iter: &mut I,
+) -> Result<u64, ProgramError>
+where
+    I: Iterator<Item = &'a AccountInfo<'b>>,
+    'b: 'a,
+{
+    match vesting_type_data.date_oracle {
+        None => Ok(vesting_type_data.vesting_schedule.now(&Clock::get()?)),
+        Some(configured_oracle) => {
+            let date_oracle = next_account_info(account_info_iter)?;
+            if date_oracle.key != &configured_oracle {
+                return Err(VestingError::InvalidDateOracle.into());
+            }
+
+            let date_oracle_data = read_from_storage::<DateOracle>(date_oracle)?;
+            if !date_oracle_data.is_initialized {
+                return Err(VestingError::DateOracleNotInitialized.into());
+            }
+
+            Ok(date_oracle_data.timestamp as u64)
+        }
+    }
+}
+
+/// When the Vesting Account configures a `realizor`, CPIs into that program
+/// to confirm it still authorizes the withdrawal (e.g. it may refuse while
+/// the beneficiary has tokens staked elsewhere). Consumes the Realizor
+/// Metadata Account and Realizor Program Account from the account list only
+/// when a realizor is configured.
+fn check_realized<'a, 'b, I>(
+    vesting_data: &VestingAccount,
+    vesting: &AccountInfo<'b>,
+    account_info_iter: &mut I,
+) -> ProgramResult
+where
+    I: Iterator<Item = &'a AccountInfo<'b>>,
+    'b: 'a,
+{
+    let realizor = match vesting_data.realizor {
+        None => return Ok(()),
+        Some(realizor) => realizor,
+    };
+
+    let metadata = next_account_info(account_info_iter)?;
+    let realizor_program = next_account_info(account_info_iter)?;
+
+    if realizor_program.key != &realizor.program {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if metadata.key != &realizor.metadata {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let is_realized_ix = Instruction {
+        program_id: realizor.program,
+        accounts: vec![
+            AccountMeta::new_readonly(*metadata.key, false),
+            AccountMeta::new_readonly(*vesting.key, false),
+        ],
+        data: vec![],
+    };
+
+    invoke(&is_realized_ix, &[metadata.clone(), vesting.clone()])
+        .map_err(|_| VestingError::UnrealizedVesting.into())
+}
+
+/// When the Vesting Type configures a `realizor` (see `SetRealizor`), CPIs
+/// into that program to confirm it still authorizes withdrawals across the
+/// whole Vesting Type, independently of any `realizor` configured on the
+/// individual Vesting Account. Consumes the Realizor Metadata Account and
+/// Realizor Program Account from the account list only when a realizor is
+/// configured.
+fn check_type_realized<'a, 'b, I>(
+    vesting_type_data: &VestingTypeAccount,
+    vesting: &AccountInfo<'b>,
+    account_info_iter: &mut I,
+) -> ProgramResult
+where
+    I: Iterator<Item = &'a AccountInfo<'b>>,
+    'b: 'a,
+{
+    let realizor = match vesting_type_data.realizor {
+        None => return Ok(()),
+        Some(realizor) => realizor,
+    };
+
+    let metadata = next_account_info(account_info_iter)?;
+    let realizor_program = next_account_info(account_info_iter)?;
+
+    if realizor_program.key != &realizor.program {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if metadata.key != &realizor.metadata {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let is_realized_ix = Instruction {
+        program_id: realizor.program,
+        accounts: vec![
+            AccountMeta::new_readonly(*metadata.key, false),
+            AccountMeta::new_readonly(*vesting.key, false),
+        ],
+        data: vec![],
+    };
+
+    invoke(&is_realized_ix, &[metadata.clone(), vesting.clone()])
+        .map_err(|_| VestingError::UnrealizedReward.into())
+}
@@ -1,19 +1,77 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, hash::hashv,
+    program_error::ProgramError, pubkey::Pubkey,
+};
 use spl_token::instruction::MAX_SIGNERS;
 
+use crate::error::VestingError;
+use crate::utils::{read_from_storage, write_to_storage_at_offset, Versioned};
+
+/// Version tag of an account that predates the introduction of `Versioned`.
+/// Such accounts (including freshly zeroed ones) carry no version byte of
+/// their own; their layout is exactly the current one minus that byte.
+const LEGACY_VERSION: u8 = 0;
+const CURRENT_VERSION: u8 = 1;
+
+/// Maximum number of external programs a single `Whitelist` account may
+/// approve, mirroring the small fixed-size convention `MAX_SIGNERS` sets
+/// for `RequiredSigners`.
+pub const MAX_WHITELISTED_PROGRAMS: usize = 10;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WithStart;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WithoutStart;
 
+/// Seconds in a day, used by `LinearVesting::daily` to turn a
+/// human-specified day count into an `unlock_period`. Shortened under
+/// `cfg(feature = "localnet")` so integration tests can simulate the
+/// passage of a day without waiting real wall-clock/slot time.
+#[cfg(not(feature = "localnet"))]
+pub const SECS_PER_DAY: u64 = 24 * 60 * 60;
+#[cfg(feature = "localnet")]
+pub const SECS_PER_DAY: u64 = 5;
+
+/// Seconds in a (30-day) month, used by `LinearVesting::monthly`. Shortened
+/// under `cfg(feature = "localnet")` for the same reason as `SECS_PER_DAY`.
+#[cfg(not(feature = "localnet"))]
+pub const SECS_PER_MONTH: u64 = 30 * SECS_PER_DAY;
+#[cfg(feature = "localnet")]
+pub const SECS_PER_MONTH: u64 = 10;
+
+/// How a `LinearVesting`'s tokens unlock between `start_time` and `last()`.
+/// `Periodic` (the default) releases tokens in discrete jumps at each
+/// `unlock_period` boundary; `Continuous` releases them smoothly every
+/// second (or slot), matching the common linear-vesting model used
+/// elsewhere in the ecosystem. `Daily`/`Monthly` are calendar-based variants
+/// of `Periodic`: rather than vesting a fraction of the first period
+/// immediately at `start_time`, they only credit a unit once it has fully
+/// elapsed (`units_elapsed = (time - start_time) / secs_per_unit`), so a
+/// grant can be specified as "vest monthly over N months" without the
+/// caller precomputing every period boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum UnlockMode {
+    Periodic,
+    Continuous,
+    Daily,
+    Monthly,
+}
+
+impl Default for UnlockMode {
+    fn default() -> Self {
+        UnlockMode::Periodic
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 pub struct LinearVesting<T = WithStart> {
     start_time: u64,                       // 8
     unlock_period: u64,                    // 8
     unlock_count: u8,                      // 1
+    mode: UnlockMode,                      // 1
     phantom: core::marker::PhantomData<T>, // 0
-} // 17 bytes
+} // 18 bytes
 
 impl LinearVesting {
     pub fn new(start_time: u64, unlock_period: u64, unlock_count: u8) -> LinearVesting<WithStart> {
@@ -21,6 +79,46 @@ impl LinearVesting {
             start_time,
             unlock_period,
             unlock_count: unlock_count.max(1),
+            mode: UnlockMode::Periodic,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// A schedule that unlocks continuously (every second/slot, rather than
+    /// in discrete `unlock_period` jumps) between `start_time` and
+    /// `end_time`.
+    pub fn continuous(start_time: u64, end_time: u64) -> LinearVesting<WithStart> {
+        LinearVesting {
+            start_time,
+            unlock_period: end_time.saturating_sub(start_time).max(1),
+            unlock_count: 2,
+            mode: UnlockMode::Continuous,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// A schedule that vests `total_days` equal daily installments, each
+    /// crediting once a full `SECS_PER_DAY` has elapsed since `start_time`
+    /// (unlike `new`, which credits the first period immediately).
+    pub fn daily(start_time: u64, total_days: u8) -> LinearVesting<WithStart> {
+        LinearVesting {
+            start_time,
+            unlock_period: SECS_PER_DAY,
+            unlock_count: total_days.max(1),
+            mode: UnlockMode::Daily,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// A schedule that vests `total_months` equal monthly installments,
+    /// each crediting once a full `SECS_PER_MONTH` has elapsed since
+    /// `start_time`.
+    pub fn monthly(start_time: u64, total_months: u8) -> LinearVesting<WithStart> {
+        LinearVesting {
+            start_time,
+            unlock_period: SECS_PER_MONTH,
+            unlock_count: total_months.max(1),
+            mode: UnlockMode::Monthly,
             phantom: core::marker::PhantomData,
         }
     }
@@ -30,6 +128,7 @@ impl LinearVesting {
             start_time: 0,
             unlock_period,
             unlock_count: unlock_count.max(1),
+            mode: UnlockMode::Periodic,
             phantom: core::marker::PhantomData,
         }
     }
@@ -43,23 +142,72 @@ impl LinearVesting {
             start_time: 0,
             unlock_count: self.unlock_count,
             unlock_period: self.unlock_period,
+            mode: self.mode,
             phantom: core::marker::PhantomData,
         }
     }
 
+    /// Checked version of `last()`. `unlock_period`/`unlock_count` come from
+    /// instruction data, so `unlock_period * (unlock_count - 1)` (and its
+    /// addition to `start_time`) must not be allowed to overflow/wrap;
+    /// returns `None` when it would.
+    fn checked_last(&self) -> Option<u64> {
+        // `Periodic`/`Continuous` credit their first unit immediately at
+        // `start_time`, so the curve finishes `unlock_count - 1` periods
+        // later. `Daily`/`Monthly` only credit a unit once it has fully
+        // elapsed, so the curve finishes a full `unlock_count` periods in.
+        let periods = match self.mode {
+            UnlockMode::Periodic | UnlockMode::Continuous => (self.unlock_count as u64) - 1,
+            UnlockMode::Daily | UnlockMode::Monthly => self.unlock_count as u64,
+        };
+        let span = self.unlock_period.checked_mul(periods)?;
+        self.start_time.checked_add(span)
+    }
+
+    /// Saturates to `u64::MAX` instead of overflowing/wrapping when the
+    /// schedule's span doesn't fit in a `u64`; such a vesting can never be
+    /// built in the first place (see `ScheduleBuilder::build`'s
+    /// `MAX_UNLOCK_SPAN` check), so this only guards ad-hoc `LinearVesting`
+    /// values constructed outside the builder.
     pub fn last(&self) -> u64 {
-        self.start_time + self.unlock_period * (self.unlock_count - 1) as u64
+        self.checked_last().unwrap_or(u64::MAX)
     }
 
-    pub fn available(&self, mut time: u64) -> f64 {
+    /// Returns how many of `tokens` have vested as of `time`, entirely in
+    /// integer arithmetic so the result is bit-identical across toolchains
+    /// and targets (unlike the `f64` computation this replaced). The
+    /// unvested remainder is floored (`remaining_periods * tokens /
+    /// unlock_count`) and `vested` is derived by subtracting it from
+    /// `tokens`, so rounding always favors leaving tokens locked rather
+    /// than releasing one extra token early.
+    pub fn vested(&self, tokens: u64, time: u64) -> u64 {
         if time < self.start_time {
-            return 0.0;
+            return 0;
+        }
+        let last = self.last();
+        if time >= last {
+            return tokens;
         }
-        if time >= self.last() {
-            return 1.0;
+
+        match self.mode {
+            UnlockMode::Periodic => {
+                let periods_passed = (time - self.start_time) / self.unlock_period + 1;
+                let remaining_periods = self.unlock_count as u64 - periods_passed;
+                let unvested = (remaining_periods as u128 * tokens as u128
+                    / self.unlock_count as u128) as u64;
+                tokens - unvested
+            }
+            UnlockMode::Continuous => {
+                let elapsed = time - self.start_time;
+                let span = last - self.start_time;
+                (tokens as u128 * elapsed as u128 / span as u128) as u64
+            }
+            UnlockMode::Daily | UnlockMode::Monthly => {
+                let units_elapsed = (time - self.start_time) / self.unlock_period;
+                let units_elapsed = units_elapsed.min(self.unlock_count as u64);
+                (tokens as u128 * units_elapsed as u128 / self.unlock_count as u128) as u64
+            }
         }
-        time -= self.start_time;
-        return self.part() * (time / self.unlock_period + 1) as f64;
     }
 
     pub fn unlock_period(&self) -> u64 {
@@ -71,15 +219,41 @@ impl LinearVesting {
     }
 }
 
-impl<T> LinearVesting<T> {
-    pub fn part(&self) -> f64 {
-        1f64 / self.unlock_count as f64
+impl Default for LinearVesting {
+    fn default() -> Self {
+        LinearVesting::new(0, 0, 0)
     }
 }
 
-impl Default for LinearVesting {
+impl LinearVesting<WithoutStart> {
+    /// Anchors a relative (`without_start`) vesting to `start_time`,
+    /// carrying its `mode` over so an `offseted`/`offseted_by` continuous
+    /// vesting doesn't silently become periodic.
+    fn with_start_time(&self, start_time: u64) -> LinearVesting<WithStart> {
+        LinearVesting {
+            start_time,
+            unlock_period: self.unlock_period,
+            unlock_count: self.unlock_count,
+            mode: self.mode,
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+/// Which `Clock` sysvar field a `VestingSchedule`'s timestamps are measured
+/// against. `Slot` gives deterministic, block-production-aligned unlock
+/// boundaries for projects that don't want releases to drift with
+/// `Clock::unix_timestamp`, the way block-number-based vesting works in
+/// Substrate's orml-vesting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum TimeBasis {
+    Timestamp,
+    Slot,
+}
+
+impl Default for TimeBasis {
     fn default() -> Self {
-        LinearVesting::new(0, 0, 0)
+        TimeBasis::Timestamp
     }
 }
 
@@ -87,14 +261,22 @@ impl Default for LinearVesting {
 pub struct VestingSchedule {
     token_count: u64,                               // 8
     vesting_count: u8,                              // 1
-    vestings: [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS], // 25 * 16 = 400
-} // 407 bvtes
+    vestings: [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS], // 26 * 16 = 416
+    time_basis: TimeBasis,                          // 1
+    cliff_ts: Option<u64>,                          // 9
+    min_period: Option<u64>,                        // 9
+    withdrawal_cap: Option<u64>,                    // 9
+} // 453 bytes
 
 #[derive(Debug, PartialEq)]
 pub struct ScheduleBuilder {
     total_tokens: u64,
     used_tokens: u64,
     vestings: Vec<(u64, LinearVesting)>,
+    time_basis: TimeBasis,
+    cliff_ts: Option<u64>,
+    min_period: Option<u64>,
+    withdrawal_cap: Option<u64>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -120,12 +302,57 @@ pub enum ScheduleBuilderError {
 
     /// Start time is bigger than end time
     InvalidTimeInterval,
+
+    /// `ending_at`'s `end_time` precedes the start of the vesting it would
+    /// truncate.
+    EndTimeBeforeStart,
+
+    /// Truncating a vesting to `end_time`, or offsetting one by the
+    /// requested amount, over/underflowed or left it with no unlock
+    /// periods at all.
+    DegenerateUnlockCount,
+
+    /// A vesting's `unlock_period` is zero, which would divide by zero
+    /// when computing how many unlocks fit before `end_time`.
+    ZeroUnlockPeriod,
+
+    /// `update_schedule`'s replacement schedule covers a different total
+    /// token amount than the schedule it would replace.
+    TotalTokensMismatch,
+
+    /// `update_schedule`'s replacement schedule is not `is_valid`.
+    InvalidSchedule,
+
+    /// `update_schedule`'s replacement schedule would reduce the amount
+    /// already unlocked as of `now`, clawing back tokens a holder could
+    /// already withdraw.
+    WouldClawBackUnlockedTokens,
+
+    /// A vesting's span (`last() - start_time`) overflowed a `u64`, or
+    /// exceeded `VestingSchedule::MAX_UNLOCK_SPAN`.
+    DurationOverflow,
+
+    /// `checkpoints`'s cumulative unlocked amount decreased from one
+    /// checkpoint to the next, which can't represent an unlock curve
+    /// (cumulative amounts only ever grow as more tokens vest).
+    CheckpointsNotNonDecreasing,
 }
 
 impl VestingSchedule {
     pub const MAX_VESTINGS: usize = 16;
 
-    pub fn new(total_tokens: u64, vestings: &[(u64, LinearVesting)]) -> VestingSchedule {
+    /// Upper bound on a single `LinearVesting`'s span (`last() -
+    /// start_time`), enforced by `ScheduleBuilder::build`. Generous enough
+    /// for any realistic vesting (100 years of seconds, or of slots at well
+    /// under one slot per second), while still keeping
+    /// `unlock_period * unlock_count` comfortably clear of `u64` overflow.
+    pub const MAX_UNLOCK_SPAN: u64 = 100 * 365 * 24 * 60 * 60;
+
+    pub fn new(
+        total_tokens: u64,
+        vestings: &[(u64, LinearVesting)],
+        time_basis: TimeBasis,
+    ) -> VestingSchedule {
         assert!(vestings.len() <= VestingSchedule::MAX_VESTINGS);
         let mut vestings_: [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS] = Default::default();
         vestings_[..vestings.len()].copy_from_slice(vestings);
@@ -133,6 +360,10 @@ impl VestingSchedule {
             token_count: total_tokens,
             vesting_count: vestings.len() as u8,
             vestings: vestings_,
+            time_basis,
+            cliff_ts: None,
+            min_period: None,
+            withdrawal_cap: None,
         }
     }
 
@@ -140,13 +371,46 @@ impl VestingSchedule {
         ScheduleBuilder::with_tokens(total_tokens)
     }
 
+    /// Timestamp before which `available` reports nothing is unlocked yet,
+    /// regardless of what the per-vesting curve below would otherwise
+    /// release. `None` means the schedule has no such gate (the common
+    /// case, and the only shape older accounts can have).
+    pub fn cliff_ts(&self) -> Option<u64> {
+        self.cliff_ts
+    }
+
+    /// Width of the rolling window `VestingAccount::calculate_withdrawable_with_cap`
+    /// rate-limits withdrawals against. `None` (the default, and the only
+    /// shape older accounts can have) means no rate limit applies.
+    pub fn min_period(&self) -> Option<u64> {
+        self.min_period
+    }
+
+    /// Maximum a beneficiary may withdraw within any `min_period` window,
+    /// independent of how much is vested. `None` means no rate limit
+    /// applies.
+    pub fn withdrawal_cap(&self) -> Option<u64> {
+        self.withdrawal_cap
+    }
+
+    /// Amount of `total_tokens` unlocked as of `time`. If `cliff_ts` is set
+    /// and `time` precedes it, returns 0 outright; once the cliff passes,
+    /// resumes the normal per-vesting calculation exactly as if the cliff
+    /// had never been set, so crossing it never double-counts or "catches
+    /// up" on tokens the curve already considered vested.
     pub fn available(&self, time: u64) -> u64 {
+        if let Some(cliff_ts) = self.cliff_ts {
+            if time < cliff_ts {
+                return 0;
+            }
+        }
+
         let mut tokens = 0;
         for tv in self.vestings.iter() {
             if tv.1.start_time > time {
                 break;
             }
-            tokens += (tv.1.available(time) * tv.0 as f64) as u64
+            tokens += tv.1.vested(tv.0, time)
         }
         tokens
     }
@@ -155,6 +419,37 @@ impl VestingSchedule {
         self.token_count
     }
 
+    /// How many of `total_tokens` are still locked as of `time`.
+    pub fn locked(&self, time: u64) -> u64 {
+        self.total_tokens().saturating_sub(self.available(time))
+    }
+
+    /// A deterministic, monotonically-decaying governance vote weight
+    /// derived from the vesting curve, following the linear-lockup model
+    /// used by voter-stake-registry: each still-locked `LinearVesting`'s
+    /// locked tokens are weighted by how much of its remaining lock
+    /// duration falls within `saturation_secs` (a full `saturation_secs` or
+    /// more left gives full weight; less gives a proportionally smaller
+    /// one), then summed. Weight reaches zero once `time >= last()` for a
+    /// given vesting.
+    pub fn voting_power(&self, time: u64, saturation_secs: u64) -> u64 {
+        if saturation_secs == 0 {
+            return 0;
+        }
+
+        let mut power: u128 = 0;
+        for &(tokens, vesting) in self.vestings() {
+            let locked_tokens = tokens.saturating_sub(vesting.vested(tokens, time));
+            if locked_tokens == 0 {
+                continue;
+            }
+
+            let remaining = vesting.last().saturating_sub(time).min(saturation_secs);
+            power += locked_tokens as u128 * remaining as u128 / saturation_secs as u128;
+        }
+        power as u64
+    }
+
     pub fn is_valid(&self) -> bool {
         if self.vesting_count as usize > VestingSchedule::MAX_VESTINGS {
             return false;
@@ -196,6 +491,45 @@ impl VestingSchedule {
     pub fn token_count(&self) -> u64 {
         self.token_count
     }
+
+    pub fn time_basis(&self) -> TimeBasis {
+        self.time_basis
+    }
+
+    /// Reads whichever `Clock` field this schedule's `time_basis` evaluates
+    /// unlocks against: `unix_timestamp` for `Timestamp`, `slot` for `Slot`.
+    pub fn now(&self, clock: &Clock) -> u64 {
+        match self.time_basis {
+            TimeBasis::Timestamp => clock.unix_timestamp as u64,
+            TimeBasis::Slot => clock.slot,
+        }
+    }
+
+    /// Consolidates two schedules (e.g. two grants into the same
+    /// `VestingTypeAccount`) into one, interleaving both `vestings` tables
+    /// by `start_time` and summing `token_count`. Goes through
+    /// `ScheduleBuilder` so the merged result is rejected exactly as any
+    /// other schedule would be if it exceeds `MAX_VESTINGS` or violates the
+    /// sorted/non-overlap invariant `is_valid` enforces.
+    pub fn merge(&self, other: &VestingSchedule) -> Result<VestingSchedule, ScheduleBuilderError> {
+        let mut vestings: Vec<(u64, LinearVesting)> = self.vestings().to_vec();
+        vestings.extend_from_slice(other.vestings());
+        vestings.sort_by_key(|vesting| vesting.1.start_time);
+
+        let mut builder =
+            ScheduleBuilder::with_tokens(self.token_count + other.token_count)
+                .time_basis(self.time_basis);
+        if let Some(cliff_ts) = self.cliff_ts {
+            builder = builder.cliff_ts(cliff_ts);
+        }
+        if let (Some(min_period), Some(withdrawal_cap)) = (self.min_period, self.withdrawal_cap) {
+            builder = builder.withdrawal_limit(min_period, withdrawal_cap);
+        }
+        for (tokens, vesting) in vestings {
+            builder = builder.add(vesting, Some(tokens));
+        }
+        builder.build()
+    }
 }
 
 impl ScheduleBuilder {
@@ -204,9 +538,40 @@ impl ScheduleBuilder {
             total_tokens,
             used_tokens: 0,
             vestings: Vec::new(),
+            time_basis: TimeBasis::Timestamp,
+            cliff_ts: None,
+            min_period: None,
+            withdrawal_cap: None,
         }
     }
 
+    /// Selects which `Clock` field the resulting schedule's timestamps are
+    /// measured against (see `TimeBasis`). Defaults to `Timestamp`.
+    pub fn time_basis(mut self, time_basis: TimeBasis) -> ScheduleBuilder {
+        self.time_basis = time_basis;
+        self
+    }
+
+    /// Gates the built schedule's `available` to 0 for any timestamp before
+    /// `timestamp`, on top of whatever vestings are added below. See
+    /// `VestingSchedule::available` for the exact semantics. Distinct from
+    /// `cliff`, which adds a discrete all-at-once unlock entry rather than
+    /// gating the whole schedule.
+    pub fn cliff_ts(mut self, timestamp: u64) -> ScheduleBuilder {
+        self.cliff_ts = Some(timestamp);
+        self
+    }
+
+    /// Caps the built schedule's withdrawals to `withdrawal_cap` tokens
+    /// within any `min_period` window, on top of whatever is vested. See
+    /// `VestingAccount::calculate_withdrawable_with_cap` for the exact
+    /// semantics.
+    pub fn withdrawal_limit(mut self, min_period: u64, withdrawal_cap: u64) -> ScheduleBuilder {
+        self.min_period = Some(min_period);
+        self.withdrawal_cap = Some(withdrawal_cap);
+        self
+    }
+
     fn use_tokens(&mut self, tokens: u64) {
         self.used_tokens += tokens;
     }
@@ -237,6 +602,137 @@ impl ScheduleBuilder {
         self.add(LinearVesting::cliff(time), tokens)
     }
 
+    /// Builds a schedule directly from an explicit, caller-supplied table of
+    /// `(unlock_timestamp, token_amount)` pairs, packing each entry as a
+    /// degenerate cliff `LinearVesting`. Useful for bespoke release tables
+    /// (front/back-loaded, irregularly spaced) that can't be expressed as a
+    /// single linear ramp.
+    pub fn steps(
+        mut self,
+        entries: &[(u64, u64)],
+    ) -> Result<ScheduleBuilder, ScheduleBuilderError> {
+        if entries.len() > VestingSchedule::MAX_VESTINGS {
+            return Err(ScheduleBuilderError::TooManyVestings);
+        }
+
+        for window in entries.windows(2) {
+            if window[1].0 <= window[0].0 {
+                return Err(ScheduleBuilderError::VestingsNotSorted);
+            }
+        }
+
+        for &(_, amount) in entries {
+            if amount == 0 {
+                return Err(ScheduleBuilderError::ZeroTokens);
+            }
+        }
+
+        for &(timestamp, amount) in entries {
+            self = self.cliff(timestamp, Some(amount));
+        }
+
+        Ok(self)
+    }
+
+    /// Builds a schedule from an arbitrary, caller-supplied list of
+    /// `(unlock_timestamp, token_amount)` pairs, sorting by timestamp and
+    /// summing amounts for duplicate timestamps, rather than forcing callers
+    /// to chain `cliff`/`offseted`/`legacy` calls by hand. Useful for
+    /// front-ends that already hold a flat release-date table (e.g. one
+    /// unlock per month for two years) and want to hand it to the program
+    /// wholesale.
+    pub fn from_unlock_points(
+        mut self,
+        points: &[(u64, u64)],
+    ) -> Result<ScheduleBuilder, ScheduleBuilderError> {
+        let mut sorted_points = points.to_vec();
+        sorted_points.sort_by_key(|&(timestamp, _)| timestamp);
+
+        let mut merged_points: Vec<(u64, u64)> = Vec::new();
+        for (timestamp, amount) in sorted_points {
+            match merged_points.last_mut() {
+                Some(last) if last.0 == timestamp => last.1 += amount,
+                _ => merged_points.push((timestamp, amount)),
+            }
+        }
+
+        if merged_points.len() > VestingSchedule::MAX_VESTINGS {
+            return Err(ScheduleBuilderError::TooManyVestings);
+        }
+
+        for &(_, amount) in &merged_points {
+            if amount == 0 {
+                return Err(ScheduleBuilderError::ZeroTokens);
+            }
+        }
+
+        let points_total: u64 = merged_points.iter().map(|&(_, amount)| amount).sum();
+        let projected_used = self.used_tokens + points_total;
+        if projected_used != self.total_tokens {
+            return Err(ScheduleBuilderError::InvalidTokenAmountUsed((
+                self.total_tokens,
+                projected_used,
+            )));
+        }
+
+        for (timestamp, amount) in merged_points {
+            self = self.cliff(timestamp, Some(amount));
+        }
+
+        Ok(self)
+    }
+
+    /// Builds a schedule from an ordered list of `(timestamp,
+    /// cumulative_unlocked_amount)` checkpoints, the natural shape for a
+    /// fully custom, non-linear curve (e.g. investor tranches) that the
+    /// fixed-period model can't express. Each checkpoint becomes a cliff
+    /// holding the *delta* since the previous one; `available` already
+    /// finds the last cliff whose `start_time <= now` and sums deltas up to
+    /// it, which is exactly "the last checkpoint at or before `now`, minus
+    /// nothing beyond it" — so no separate lookup logic is needed.
+    ///
+    /// Requires strictly increasing timestamps and non-decreasing
+    /// cumulative amounts, and that the final checkpoint's amount accounts
+    /// for every token this builder still has to place.
+    pub fn checkpoints(
+        mut self,
+        checkpoints: &[(u64, u64)],
+    ) -> Result<ScheduleBuilder, ScheduleBuilderError> {
+        if checkpoints.len() > VestingSchedule::MAX_VESTINGS {
+            return Err(ScheduleBuilderError::TooManyVestings);
+        }
+
+        for window in checkpoints.windows(2) {
+            if window[1].0 <= window[0].0 {
+                return Err(ScheduleBuilderError::VestingsNotSorted);
+            }
+            if window[1].1 < window[0].1 {
+                return Err(ScheduleBuilderError::CheckpointsNotNonDecreasing);
+            }
+        }
+
+        if let Some(&(_, total)) = checkpoints.last() {
+            let projected_used = self.used_tokens + total;
+            if projected_used != self.total_tokens {
+                return Err(ScheduleBuilderError::InvalidTokenAmountUsed((
+                    self.total_tokens,
+                    projected_used,
+                )));
+            }
+        }
+
+        let mut previous_cumulative = 0;
+        for &(timestamp, cumulative) in checkpoints {
+            let delta = cumulative - previous_cumulative;
+            previous_cumulative = cumulative;
+            if delta > 0 {
+                self = self.cliff(timestamp, Some(delta));
+            }
+        }
+
+        Ok(self)
+    }
+
     pub fn offseted_by(
         self,
         offset: u64,
@@ -245,14 +741,14 @@ impl ScheduleBuilder {
     ) -> Result<ScheduleBuilder, ScheduleBuilderError> {
         match self.vestings.last() {
             None => Err(ScheduleBuilderError::EmptyBuilder),
-            Some(&x) => Ok(self.add(
-                LinearVesting::new(
-                    x.1.last() + offset,
-                    vesting.unlock_period,
-                    vesting.unlock_count,
-                ),
-                tokens,
-            )),
+            Some(&x) => {
+                let start_time = x
+                    .1
+                    .last()
+                    .checked_add(offset)
+                    .ok_or(ScheduleBuilderError::DegenerateUnlockCount)?;
+                Ok(self.add(vesting.with_start_time(start_time), tokens))
+            }
         }
     }
 
@@ -273,13 +769,27 @@ impl ScheduleBuilder {
             Ok(self)
         } else {
             let last_vesting = self.remove_last().unwrap();
-            let new_unlock_count =
-                1 + ((end_time - last_vesting.1.start_time) / last_vesting.1.unlock_period) as u8;
-            assert!(new_unlock_count < last_vesting.1.unlock_count);
+            let elapsed = end_time
+                .checked_sub(last_vesting.1.start_time)
+                .ok_or(ScheduleBuilderError::EndTimeBeforeStart)?;
+            if last_vesting.1.unlock_period == 0 {
+                return Err(ScheduleBuilderError::ZeroUnlockPeriod);
+            }
+            let elapsed_periods = elapsed / last_vesting.1.unlock_period;
+            if elapsed_periods >= u8::MAX as u64 {
+                return Err(ScheduleBuilderError::DegenerateUnlockCount);
+            }
+            let new_unlock_count = elapsed_periods as u8 + 1;
+            if new_unlock_count >= last_vesting.1.unlock_count {
+                return Err(ScheduleBuilderError::DegenerateUnlockCount);
+            }
 
             let linear_tokens =
                 last_vesting.0 * new_unlock_count as u64 / last_vesting.1.unlock_count as u64;
-            let cliff_tokens = last_vesting.0 - linear_tokens;
+            let cliff_tokens = last_vesting
+                .0
+                .checked_sub(linear_tokens)
+                .ok_or(ScheduleBuilderError::DegenerateUnlockCount)?;
 
             Ok(self
                 .add(
@@ -306,6 +816,9 @@ impl ScheduleBuilder {
         if start_time >= end_time {
             return Err(ScheduleBuilderError::InvalidTimeInterval);
         }
+        if unlock_period == 0 {
+            return Err(ScheduleBuilderError::ZeroUnlockPeriod);
+        }
         let tokens = tokens.unwrap_or(self.available_tokens());
         if initial_unlock_tokens >= tokens {
             return Err(ScheduleBuilderError::InitialUnlockTooBig);
@@ -319,29 +832,40 @@ impl ScheduleBuilder {
         } else {
             self
         };
-        let mut remaining_tokens = tokens - initial_unlock_tokens;
-
-        let mut total_linear_unlocks: u8 = 1 + ((end_time - start_time) / unlock_period) as u8;
-        if (end_time - start_time) % unlock_period != 0 {
+        let mut remaining_tokens = tokens
+            .checked_sub(initial_unlock_tokens)
+            .ok_or(ScheduleBuilderError::InitialUnlockTooBig)?;
+
+        let elapsed = end_time
+            .checked_sub(start_time)
+            .ok_or(ScheduleBuilderError::InvalidTimeInterval)?;
+        let mut total_linear_unlocks: u8 = 1 + (elapsed / unlock_period) as u8;
+        if elapsed % unlock_period != 0 {
             total_linear_unlocks += 1;
         }
 
-        let unlocks_before_cliff: u8 = 1 + ((cliff - start_time) / unlock_period) as u8;
+        let elapsed_before_cliff = start_time
+            .checked_sub(cliff)
+            .ok_or(ScheduleBuilderError::InvalidTimeInterval)?;
+        let unlocks_before_cliff: u8 = 1 + (elapsed_before_cliff / unlock_period) as u8;
         if unlocks_before_cliff > 0 {
             let tokens_at_cliff =
                 remaining_tokens * unlocks_before_cliff as u64 / total_linear_unlocks as u64;
-            remaining_tokens -= tokens_at_cliff;
+            remaining_tokens = remaining_tokens
+                .checked_sub(tokens_at_cliff)
+                .ok_or(ScheduleBuilderError::DegenerateUnlockCount)?;
             builder = builder.cliff(cliff, Some(tokens_at_cliff))
         }
 
-        let first_linear_unlock = cliff + cliff % unlock_period;
+        let first_linear_unlock = cliff
+            .checked_add(cliff % unlock_period)
+            .ok_or(ScheduleBuilderError::DegenerateUnlockCount)?;
+        let remaining_linear_unlocks = total_linear_unlocks
+            .checked_sub(unlocks_before_cliff)
+            .ok_or(ScheduleBuilderError::DegenerateUnlockCount)?;
         builder
             .add(
-                LinearVesting::new(
-                    first_linear_unlock,
-                    unlock_period,
-                    total_linear_unlocks - unlocks_before_cliff,
-                ),
+                LinearVesting::new(first_linear_unlock, unlock_period, remaining_linear_unlocks),
                 Some(remaining_tokens),
             )
             .ending_at(end_time)
@@ -375,124 +899,1394 @@ impl ScheduleBuilder {
             if i.0 == 0 {
                 return Err(ScheduleBuilderError::ZeroTokens);
             }
+
+            match i.1.checked_last() {
+                Some(last) if last - i.1.start_time <= VestingSchedule::MAX_UNLOCK_SPAN => {}
+                _ => return Err(ScheduleBuilderError::DurationOverflow),
+            }
         }
 
-        Ok(VestingSchedule::new(self.total_tokens, &self.vestings))
+        let mut schedule = VestingSchedule::new(self.total_tokens, &self.vestings, self.time_basis);
+        schedule.cliff_ts = self.cliff_ts;
+        schedule.min_period = self.min_period;
+        schedule.withdrawal_cap = self.withdrawal_cap;
+        Ok(schedule)
     }
 }
 
 #[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
 pub struct VestingTypeAccount {
+    pub version: u8,                       // 1
     pub is_initialized: bool,              // 1
-    pub vesting_schedule: VestingSchedule, // 416
+    pub vesting_schedule: VestingSchedule, // 453
     pub locked_tokens_amount: u64,         // 8
     pub administrator: Pubkey,             // 32
     pub token_pool: Pubkey,                // 32
-} // 489 bytes
+    pub date_oracle: Option<Pubkey>,       // 33
+    pub revocable: bool,                   // 1
+    pub is_revoked: bool,                  // 1
+    pub realizor: Option<Realizor>,        // 1 + 64
+    pub withdrawal_timelock: i64,          // 8
+    pub whitelisted_tokens_amount: u64,    // 8
+} // 643 bytes
+
+/// Frozen pre-`mode` shape of `LinearVesting`, kept only so the schedule
+/// mirrors below can still deserialize accounts written before continuous
+/// unlocking existed; every such vesting is, by construction,
+/// `UnlockMode::Periodic`.
+#[derive(Default, BorshDeserialize)]
+struct LinearVestingV0 {
+    start_time: u64,
+    unlock_period: u64,
+    unlock_count: u8,
+}
 
-#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
-pub struct RequiredSigners {
-    pub is_initialized: bool,                   // 1
-    pub require_signers: [Pubkey; MAX_SIGNERS], // 32 * 11
-    pub require_number: u8,                     // 1
-    pub all_number: u8,                         // 1
-    pub vesting_type_account: Pubkey,           // 32
-} // 387 bytes
+impl From<LinearVestingV0> for LinearVesting {
+    fn from(legacy: LinearVestingV0) -> Self {
+        LinearVesting {
+            start_time: legacy.start_time,
+            unlock_period: legacy.unlock_period,
+            unlock_count: legacy.unlock_count,
+            mode: UnlockMode::Periodic,
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
 
-#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
-pub struct CurrentSigners {
-    pub is_initialized: bool,                 // 1
-    pub current_signers: [bool; MAX_SIGNERS], // 1 * 11
-    pub vesting_account: Pubkey,              // 32
-} // 44 bytes
+fn migrate_vestings(
+    legacy: [(u64, LinearVestingV0); VestingSchedule::MAX_VESTINGS],
+) -> [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS] {
+    let mut vestings: [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS] = Default::default();
+    for (dst, (tokens, vesting)) in vestings.iter_mut().zip(legacy) {
+        *dst = (tokens, vesting.into());
+    }
+    vestings
+}
 
-#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
-pub struct VestingAccount {
-    pub is_initialized: bool,         // 1
-    pub total_tokens: u64,            // 8
-    pub withdrawn_tokens: u64,        // 8
-    pub token_account: Pubkey,        // 32
-    pub vesting_type_account: Pubkey, // 32
-} // 81 bytes
+/// Frozen pre-`time_basis` shape of `VestingSchedule`, kept only so the
+/// `VestingTypeAccount` legacy mirrors below can still deserialize accounts
+/// written before this field existed; every such schedule is, by
+/// construction, measured against `Clock::unix_timestamp`.
+#[derive(Default, BorshDeserialize)]
+struct VestingScheduleV0 {
+    token_count: u64,
+    vesting_count: u8,
+    vestings: [(u64, LinearVestingV0); VestingSchedule::MAX_VESTINGS],
+}
 
-impl VestingAccount {
-    pub fn calculate_available_to_withdraw_amount(
-        &self,
-        schedule: &VestingSchedule,
-        now: u64,
-    ) -> u64 {
-        let unlocked_amount = schedule.available(now);
-        let unlocked_amount = unlocked_amount.min(self.total_tokens); // safeguard check
-        unlocked_amount.saturating_sub(self.withdrawn_tokens)
+impl From<VestingScheduleV0> for VestingSchedule {
+    fn from(legacy: VestingScheduleV0) -> Self {
+        VestingSchedule {
+            token_count: legacy.token_count,
+            vesting_count: legacy.vesting_count,
+            vestings: migrate_vestings(legacy.vestings),
+            time_basis: TimeBasis::Timestamp,
+            cliff_ts: None,
+            min_period: None,
+            withdrawal_cap: None,
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Frozen pre-`mode` shape of `VestingSchedule`, i.e. the shape as of
+/// `VestingTypeAccount` version 3 (after `time_basis`, before
+/// `LinearVesting::mode`).
+#[derive(Default, BorshDeserialize)]
+struct VestingScheduleV1 {
+    token_count: u64,
+    vesting_count: u8,
+    vestings: [(u64, LinearVestingV0); VestingSchedule::MAX_VESTINGS],
+    time_basis: TimeBasis,
+}
 
-    #[test]
-    fn test_builder_success() {
-        let cliff = 20_000;
-        let offseted_by = 30_000;
-        let standalone = 200_000;
+impl From<VestingScheduleV1> for VestingSchedule {
+    fn from(legacy: VestingScheduleV1) -> Self {
+        VestingSchedule {
+            token_count: legacy.token_count,
+            vesting_count: legacy.vesting_count,
+            vestings: migrate_vestings(legacy.vestings),
+            time_basis: legacy.time_basis,
+            cliff_ts: None,
+            min_period: None,
+            withdrawal_cap: None,
+        }
+    }
+}
 
-        let schedule = VestingSchedule::with_tokens(1_000_000)
-            .cliff(cliff, Some(100_000))
-            .offseted_by(
-                offseted_by,
-                LinearVesting::without_start(10_000, 3),
-                Some(100_000),
-            )
-            .map(|x| x.offseted(LinearVesting::without_start(20_000, 5), Some(100_000)))
-            .and_then(|x| match x {
-                Err(e) => Err(e),
-                Ok(x) => Ok(x.add(LinearVesting::new(standalone, 10_000, 2), None)),
-            })
-            .and_then(|x| x.build());
-        assert!(schedule.is_ok());
+/// Frozen pre-`cliff_ts` shape of `VestingSchedule`, i.e. the shape as of
+/// `VestingTypeAccount` version 4 (after `LinearVesting::mode`, before the
+/// schedule-wide unlock gate). Every such schedule is, by construction,
+/// ungated.
+#[derive(Default, BorshDeserialize)]
+struct VestingScheduleV2 {
+    token_count: u64,
+    vesting_count: u8,
+    vestings: [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS],
+    time_basis: TimeBasis,
+}
 
-        let schedule = schedule.unwrap();
-        assert_eq!(schedule.total_tokens(), 1_000_000);
-        assert_eq!(
-            &schedule.vestings[..schedule.vesting_count as usize],
-            &[
-                (100_000, LinearVesting::cliff(cliff)),
-                (100_000, LinearVesting::new(cliff + offseted_by, 10_000, 3)),
-                (
-                    100_000,
-                    LinearVesting::new(cliff + offseted_by + 10_000 * (3 - 1) + 20_000, 20_000, 5)
-                ),
-                (700_000, LinearVesting::new(standalone, 10_000, 2)),
-            ]
-        )
+impl From<VestingScheduleV2> for VestingSchedule {
+    fn from(legacy: VestingScheduleV2) -> Self {
+        VestingSchedule {
+            token_count: legacy.token_count,
+            vesting_count: legacy.vesting_count,
+            vestings: legacy.vestings,
+            time_basis: legacy.time_basis,
+            cliff_ts: None,
+            min_period: None,
+            withdrawal_cap: None,
+        }
     }
+}
 
-    #[test]
-    fn test_builder_failure_offset() {
-        let schedule = VestingSchedule::with_tokens(1_000_000).offseted_by(
-            10_000,
-            LinearVesting::without_start(10_000, 3),
-            None,
-        );
-        assert_eq!(schedule, Err(ScheduleBuilderError::EmptyBuilder))
-    }
+/// Frozen pre-rate-limit shape of `VestingSchedule`, i.e. the shape as of
+/// `VestingTypeAccount` version 5 (after the schedule-wide unlock gate,
+/// before `min_period`/`withdrawal_cap`). Every such schedule is, by
+/// construction, unrate-limited.
+#[derive(Default, BorshDeserialize)]
+struct VestingScheduleV3 {
+    token_count: u64,
+    vesting_count: u8,
+    vestings: [(u64, LinearVesting); VestingSchedule::MAX_VESTINGS],
+    time_basis: TimeBasis,
+    cliff_ts: Option<u64>,
+}
 
-    #[test]
-    fn test_builder_failure_remaining_tokens() {
-        let schedule = VestingSchedule::with_tokens(1_000_000)
-            .cliff(10_000, Some(100_000))
-            .build();
-        assert_eq!(
-            schedule,
-            Err(ScheduleBuilderError::InvalidTokenAmountUsed((
-                1_000_000, 100_000
-            )))
-        )
+impl From<VestingScheduleV3> for VestingSchedule {
+    fn from(legacy: VestingScheduleV3) -> Self {
+        VestingSchedule {
+            token_count: legacy.token_count,
+            vesting_count: legacy.vesting_count,
+            vestings: legacy.vestings,
+            time_basis: legacy.time_basis,
+            cliff_ts: legacy.cliff_ts,
+            min_period: None,
+            withdrawal_cap: None,
+        }
     }
+}
 
-    #[test]
-    fn test_builder_failure_unsorted_vestings() {
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV0 {
+    is_initialized: bool,
+    vesting_schedule: VestingScheduleV0,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+}
+
+/// Shape of `VestingTypeAccount` before `revoke_vesting_type` was added,
+/// i.e. every vesting type created as irrevocable.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV1 {
+    is_initialized: bool,
+    vesting_schedule: VestingScheduleV0,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+}
+
+/// Shape of `VestingTypeAccount` before `VestingSchedule` gained `time_basis`,
+/// i.e. every vesting type created against `Clock::unix_timestamp`.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV2 {
+    is_initialized: bool,
+    vesting_schedule: VestingScheduleV0,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    is_revoked: bool,
+}
+
+/// Shape of `VestingTypeAccount` before `LinearVesting` gained `mode`
+/// (continuous unlock support), i.e. every vesting created as
+/// `UnlockMode::Periodic`.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV3 {
+    is_initialized: bool,
+    vesting_schedule: VestingScheduleV1,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    is_revoked: bool,
+}
+
+/// Shape of `VestingTypeAccount` before `VestingSchedule` gained `cliff_ts`
+/// (the schedule-wide unlock gate), i.e. every vesting type created
+/// without one.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV4 {
+    is_initialized: bool,
+    vesting_schedule: VestingScheduleV2,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    is_revoked: bool,
+}
+
+/// Shape of `VestingTypeAccount` before `VestingSchedule` gained
+/// `min_period`/`withdrawal_cap` (the per-window withdrawal rate limit),
+/// i.e. every vesting type created with withdrawals unlimited beyond what
+/// is vested.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV5 {
+    is_initialized: bool,
+    vesting_schedule: VestingScheduleV3,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    is_revoked: bool,
+}
+
+/// Shape of `VestingTypeAccount` before it gained its own `realizor`
+/// (distinct from the per-`VestingAccount` one set at `CreateVestingAccount`
+/// time), i.e. every vesting type created with no type-wide withdrawal gate.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV6 {
+    is_initialized: bool,
+    vesting_schedule: VestingSchedule,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    is_revoked: bool,
+}
+
+/// Shape of `VestingTypeAccount` before it gained `withdrawal_timelock`
+/// (the minimum gap between successive withdrawals from a single
+/// `VestingAccount`), i.e. every vesting type created with withdrawals
+/// unthrottled in time.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV7 {
+    is_initialized: bool,
+    vesting_schedule: VestingSchedule,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    is_revoked: bool,
+    realizor: Option<Realizor>,
+}
+
+/// Shape of `VestingTypeAccount` before it gained `whitelisted_tokens_amount`
+/// (the running total of tokens staked out across every `VestingAccount`
+/// under this type via `WhitelistWithdraw`/`WhitelistDeposit`), i.e. every
+/// vesting type created before the pool's real balance could be tracked as
+/// under-collateralized relative to `locked_tokens_amount`.
+#[derive(Default, BorshDeserialize)]
+struct VestingTypeAccountV8 {
+    is_initialized: bool,
+    vesting_schedule: VestingSchedule,
+    locked_tokens_amount: u64,
+    administrator: Pubkey,
+    token_pool: Pubkey,
+    date_oracle: Option<Pubkey>,
+    revocable: bool,
+    is_revoked: bool,
+    realizor: Option<Realizor>,
+    withdrawal_timelock: i64,
+}
+
+impl Versioned for VestingTypeAccount {
+    // Bumped past `CURRENT_VERSION` because this struct alone gained the
+    // `revocable`/`is_revoked` fields, then its own `realizor`, then
+    // `withdrawal_timelock`, then `whitelisted_tokens_amount`; its embedded
+    // `VestingSchedule` gained `time_basis`, then `cliff_ts`, then
+    // `min_period`/`withdrawal_cap`; and its embedded `LinearVesting`s
+    // gained `mode`. The other account types are untouched by any of these
+    // changes.
+    const VERSION: u8 = 9;
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            LEGACY_VERSION => {
+                let legacy = VestingTypeAccountV0::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: legacy.is_initialized,
+                    vesting_schedule: legacy.vesting_schedule.into(),
+                    locked_tokens_amount: legacy.locked_tokens_amount,
+                    administrator: legacy.administrator,
+                    token_pool: legacy.token_pool,
+                    date_oracle: legacy.date_oracle,
+                    revocable: false,
+                    is_revoked: false,
+                    realizor: None,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            1 => {
+                let previous = VestingTypeAccountV1::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule.into(),
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: false,
+                    is_revoked: false,
+                    realizor: None,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            2 => {
+                let previous = VestingTypeAccountV2::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule.into(),
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: previous.revocable,
+                    is_revoked: previous.is_revoked,
+                    realizor: None,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            3 => {
+                let previous = VestingTypeAccountV3::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule.into(),
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: previous.revocable,
+                    is_revoked: previous.is_revoked,
+                    realizor: None,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            4 => {
+                let previous = VestingTypeAccountV4::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule.into(),
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: previous.revocable,
+                    is_revoked: previous.is_revoked,
+                    realizor: None,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            5 => {
+                let previous = VestingTypeAccountV5::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule.into(),
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: previous.revocable,
+                    is_revoked: previous.is_revoked,
+                    realizor: None,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            6 => {
+                let previous = VestingTypeAccountV6::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule,
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: previous.revocable,
+                    is_revoked: previous.is_revoked,
+                    realizor: None,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            7 => {
+                let previous = VestingTypeAccountV7::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule,
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: previous.revocable,
+                    is_revoked: previous.is_revoked,
+                    realizor: previous.realizor,
+                    withdrawal_timelock: 0,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            8 => {
+                let previous = VestingTypeAccountV8::deserialize(&mut &data[..])?;
+                Ok(VestingTypeAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    vesting_schedule: previous.vesting_schedule,
+                    locked_tokens_amount: previous.locked_tokens_amount,
+                    administrator: previous.administrator,
+                    token_pool: previous.token_pool,
+                    date_oracle: previous.date_oracle,
+                    revocable: previous.revocable,
+                    is_revoked: previous.is_revoked,
+                    realizor: previous.realizor,
+                    withdrawal_timelock: previous.withdrawal_timelock,
+                    whitelisted_tokens_amount: 0,
+                })
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl VestingTypeAccount {
+    /// Replaces `vesting_schedule` with `new`, e.g. to extend an end date or
+    /// correct a cliff after creation, without letting any holder's
+    /// already-unlocked entitlement become inconsistent: `new` must cover
+    /// the same total token amount, be `is_valid`, and not unlock fewer
+    /// tokens than the current schedule does as of `now`.
+    pub fn update_schedule(
+        &mut self,
+        new: VestingSchedule,
+        now: u64,
+    ) -> Result<(), ScheduleBuilderError> {
+        if new.total_tokens() != self.vesting_schedule.total_tokens() {
+            return Err(ScheduleBuilderError::TotalTokensMismatch);
+        }
+
+        if !new.is_valid() {
+            return Err(ScheduleBuilderError::InvalidSchedule);
+        }
+
+        if new.available(now) < self.vesting_schedule.available(now) {
+            return Err(ScheduleBuilderError::WouldClawBackUnlockedTokens);
+        }
+
+        self.vesting_schedule = new;
+        Ok(())
+    }
+}
+
+/// A small account maintained by a trusted off-chain signer, used in place of
+/// the `Clock` sysvar when a `VestingTypeAccount` wants unlocks to follow a
+/// published real-world date rather than validator block time.
+#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct DateOracle {
+    pub version: u8,           // 1
+    pub is_initialized: bool,  // 1
+    pub authority: Pubkey,     // 32
+    pub timestamp: i64,        // 8
+} // 42 bytes
+
+#[derive(Default, BorshDeserialize)]
+struct DateOracleV0 {
+    is_initialized: bool,
+    authority: Pubkey,
+    timestamp: i64,
+}
+
+impl Versioned for DateOracle {
+    const VERSION: u8 = CURRENT_VERSION;
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            LEGACY_VERSION => {
+                let legacy = DateOracleV0::deserialize(&mut &data[..])?;
+                Ok(DateOracle {
+                    version: CURRENT_VERSION,
+                    is_initialized: legacy.is_initialized,
+                    authority: legacy.authority,
+                    timestamp: legacy.timestamp,
+                })
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct RequiredSigners {
+    pub version: u8,                             // 1
+    pub is_initialized: bool,                    // 1
+    pub require_signers: [Pubkey; MAX_SIGNERS],  // 32 * 11
+    pub require_number: u8,                      // 1
+    pub all_number: u8,                          // 1
+    pub vesting_type_account: Pubkey,            // 32
+    pub pending_action: [u8; 32],                // 32
+    pub pending_approvals: [bool; MAX_SIGNERS],  // 1 * 11
+    pub weights: [u8; MAX_SIGNERS],              // 1 * 11
+} // 442 bytes
+
+#[derive(Default, BorshDeserialize)]
+struct RequiredSignersV0 {
+    is_initialized: bool,
+    require_signers: [Pubkey; MAX_SIGNERS],
+    require_number: u8,
+    all_number: u8,
+    vesting_type_account: Pubkey,
+}
+
+/// Shape of `RequiredSigners` as of the `Versioned` introduction, before
+/// `pending_action`/`pending_approvals` were added for committee-gated
+/// privileged instructions.
+#[derive(Default, BorshDeserialize)]
+struct RequiredSignersV1 {
+    version: u8,
+    is_initialized: bool,
+    require_signers: [Pubkey; MAX_SIGNERS],
+    require_number: u8,
+    all_number: u8,
+    vesting_type_account: Pubkey,
+}
+
+/// Shape of `RequiredSigners` as of the pending-action-approval fields,
+/// before every signer was given its own `weights` entry and `require_number`
+/// became a cumulative weight threshold rather than a plain signer count.
+#[derive(Default, BorshDeserialize)]
+struct RequiredSignersV2 {
+    version: u8,
+    is_initialized: bool,
+    require_signers: [Pubkey; MAX_SIGNERS],
+    require_number: u8,
+    all_number: u8,
+    vesting_type_account: Pubkey,
+    pending_action: [u8; 32],
+    pending_approvals: [bool; MAX_SIGNERS],
+}
+
+impl Versioned for RequiredSigners {
+    // Bumped past `CURRENT_VERSION` because this struct alone gained the
+    // pending-action-approval fields below (and, since, `weights`); the
+    // other four account types are untouched by this change.
+    const VERSION: u8 = 3;
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            LEGACY_VERSION => {
+                let legacy = RequiredSignersV0::deserialize(&mut &data[..])?;
+                Ok(RequiredSigners {
+                    version: Self::VERSION,
+                    is_initialized: legacy.is_initialized,
+                    require_signers: legacy.require_signers,
+                    require_number: legacy.require_number,
+                    all_number: legacy.all_number,
+                    vesting_type_account: legacy.vesting_type_account,
+                    pending_action: [0; 32],
+                    pending_approvals: [false; MAX_SIGNERS],
+                    weights: [1; MAX_SIGNERS],
+                })
+            }
+            1 => {
+                let previous = RequiredSignersV1::deserialize(&mut &data[..])?;
+                Ok(RequiredSigners {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    require_signers: previous.require_signers,
+                    require_number: previous.require_number,
+                    all_number: previous.all_number,
+                    vesting_type_account: previous.vesting_type_account,
+                    pending_action: [0; 32],
+                    pending_approvals: [false; MAX_SIGNERS],
+                    weights: [1; MAX_SIGNERS],
+                })
+            }
+            2 => {
+                let previous = RequiredSignersV2::deserialize(&mut &data[..])?;
+                Ok(RequiredSigners {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    require_signers: previous.require_signers,
+                    require_number: previous.require_number,
+                    all_number: previous.all_number,
+                    vesting_type_account: previous.vesting_type_account,
+                    pending_action: previous.pending_action,
+                    pending_approvals: previous.pending_approvals,
+                    weights: [1; MAX_SIGNERS],
+                })
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl RequiredSigners {
+    /// Derives a stable identifier for "this exact privileged action": the
+    /// Vesting Type it targets together with the full packed instruction
+    /// that would perform it. Changing any argument of the instruction (or
+    /// targeting a different Vesting Type) yields a different hash, so
+    /// approvals collected for one proposal never carry over to another.
+    pub fn action_hash(vesting_type: &Pubkey, instruction_data: &[u8]) -> [u8; 32] {
+        hashv(&[vesting_type.as_ref(), instruction_data]).to_bytes()
+    }
+
+    /// Registers `signer`'s approval of `action`. Approving a different
+    /// action than the one currently pending discards whatever approvals
+    /// were already collected, since those were given for different
+    /// arguments.
+    pub fn approve(&mut self, signer: &Pubkey, action: [u8; 32]) -> Result<(), ProgramError> {
+        let index = self
+            .require_signers
+            .iter()
+            .position(|required_signer| required_signer == signer)
+            .ok_or(ProgramError::MissingRequiredSignature)?;
+
+        if self.pending_action != action {
+            self.pending_action = action;
+            self.pending_approvals = [false; MAX_SIGNERS];
+        }
+        self.pending_approvals[index] = true;
+
+        Ok(())
+    }
+
+    /// Whether `action` is the pending action and its approvers' `weights`
+    /// sum to at least `require_number`. Mirrors `sign_devesting`'s
+    /// `validate_signers`, which gates devesting against the same two
+    /// fields — keeping both weighted the same way is what lets an
+    /// administrator set `require_number` above `all_number` (e.g. "the
+    /// founder alone suffices") without bricking approval of every other
+    /// privileged action gated through this method.
+    pub fn is_approved(&self, action: [u8; 32]) -> bool {
+        let approved_weight: u32 = self
+            .pending_approvals
+            .iter()
+            .zip(self.weights.iter())
+            .fold(0, |sum, (approved, weight)| {
+                if *approved {
+                    sum + *weight as u32
+                } else {
+                    sum
+                }
+            });
+
+        self.pending_action == action && approved_weight >= self.require_number as u32
+    }
+
+    /// Clears the pending action once it has been consumed by the
+    /// instruction it gated, so its approvals can't be replayed.
+    pub fn clear_pending_action(&mut self) {
+        self.pending_action = [0; 32];
+        self.pending_approvals = [false; MAX_SIGNERS];
+    }
+}
+
+#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct CurrentSigners {
+    pub version: u8,                            // 1
+    pub is_initialized: bool,                   // 1
+    pub current_signers: [bool; MAX_SIGNERS],   // 1 * 11
+    pub vesting_account: Pubkey,                // 32
+    pub nonce: u64,                             // 8
+} // 53 bytes
+
+#[derive(Default, BorshDeserialize)]
+struct CurrentSignersV0 {
+    is_initialized: bool,
+    current_signers: [bool; MAX_SIGNERS],
+    vesting_account: Pubkey,
+}
+
+/// Shape of `CurrentSigners` before the replay-protection `nonce` was added.
+#[derive(Default, BorshDeserialize)]
+struct CurrentSignersV1 {
+    version: u8,
+    is_initialized: bool,
+    current_signers: [bool; MAX_SIGNERS],
+    vesting_account: Pubkey,
+}
+
+impl Versioned for CurrentSigners {
+    // Bumped past `CURRENT_VERSION` because this struct alone gained the
+    // `nonce` field below; the other account types are untouched by this
+    // change.
+    const VERSION: u8 = 2;
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            LEGACY_VERSION => {
+                let legacy = CurrentSignersV0::deserialize(&mut &data[..])?;
+                Ok(CurrentSigners {
+                    version: Self::VERSION,
+                    is_initialized: legacy.is_initialized,
+                    current_signers: legacy.current_signers,
+                    vesting_account: legacy.vesting_account,
+                    nonce: 0,
+                })
+            }
+            1 => {
+                let previous = CurrentSignersV1::deserialize(&mut &data[..])?;
+                Ok(CurrentSigners {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    current_signers: previous.current_signers,
+                    vesting_account: previous.vesting_account,
+                    nonce: 0,
+                })
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// Approved list of external programs a `VestingAccount`'s beneficiary may
+/// move still-locked tokens into via `WhitelistWithdraw`/`WhitelistDeposit`,
+/// e.g. a staking or governance program. Managed by the Vesting Type's
+/// administrator through `AddToWhitelist`/`RemoveFromWhitelist`.
+#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Whitelist {
+    pub version: u8,                                   // 1
+    pub is_initialized: bool,                          // 1
+    pub vesting_type_account: Pubkey,                  // 32
+    pub programs: [Pubkey; MAX_WHITELISTED_PROGRAMS],  // 32 * 10
+    pub count: u8,                                      // 1
+} // 355 bytes
+
+#[derive(Default, BorshDeserialize)]
+struct WhitelistV0 {
+    is_initialized: bool,
+    vesting_type_account: Pubkey,
+    programs: [Pubkey; MAX_WHITELISTED_PROGRAMS],
+    count: u8,
+}
+
+impl Versioned for Whitelist {
+    const VERSION: u8 = CURRENT_VERSION;
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            LEGACY_VERSION => {
+                let legacy = WhitelistV0::deserialize(&mut &data[..])?;
+                Ok(Whitelist {
+                    version: CURRENT_VERSION,
+                    is_initialized: legacy.is_initialized,
+                    vesting_type_account: legacy.vesting_type_account,
+                    programs: legacy.programs,
+                    count: legacy.count,
+                })
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl Whitelist {
+    pub fn contains(&self, program: &Pubkey) -> bool {
+        self.programs[..self.count as usize].contains(program)
+    }
+
+    pub fn add(&mut self, program: Pubkey) -> Result<(), ProgramError> {
+        if self.contains(&program) {
+            return Err(VestingError::ProgramAlreadyWhitelisted.into());
+        }
+
+        if self.count as usize >= MAX_WHITELISTED_PROGRAMS {
+            return Err(VestingError::WhitelistFull.into());
+        }
+
+        self.programs[self.count as usize] = program;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, program: &Pubkey) -> Result<(), ProgramError> {
+        let index = self.programs[..self.count as usize]
+            .iter()
+            .position(|whitelisted| whitelisted == program)
+            .ok_or(VestingError::ProgramNotWhitelisted)?;
+
+        let last = self.count as usize - 1;
+        self.programs[index] = self.programs[last];
+        self.programs[last] = Pubkey::default();
+        self.count -= 1;
+
+        Ok(())
+    }
+}
+
+/// Maximum number of `WithdrawalEntry` a single `WithdrawalLog` can hold
+/// before `WithdrawalLog::record` starts wrapping back to index 0 and
+/// overwriting the oldest entry.
+pub const MAX_WITHDRAWAL_RECORDS: usize = 128;
+
+/// One withdrawal appended to a `WithdrawalLog` by `WithdrawFromVesting` or
+/// `WithdrawExcessiveFromPool`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct WithdrawalEntry {
+    pub slot: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub running_total: u64,
+}
+
+/// Append-only on-chain audit log of withdrawals against a single Vesting
+/// Type, initialized via `InitWithdrawalLog` and then passed alongside every
+/// `WithdrawFromVesting`/`WithdrawExcessiveFromPool` call so integrators can
+/// read a structured history back without parsing transaction logs.
+/// `entries` is a fixed-capacity ring buffer — once full, `record` wraps
+/// `next_index` back to 0 and starts overwriting the oldest entry, so
+/// `total_recorded` (which never wraps) is the only way to tell how many
+/// withdrawals actually happened versus how many of the most recent ones
+/// are still present.
+#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct WithdrawalLog {
+    pub version: u8,                                        // 1
+    pub is_initialized: bool,                                // 1
+    pub vesting_type_account: Pubkey,                         // 32
+    pub next_index: u32,                                      // 4
+    pub total_recorded: u64,                                  // 8
+    pub total_withdrawn: u64,                                 // 8
+    pub entries: [WithdrawalEntry; MAX_WITHDRAWAL_RECORDS],   // 56 * 128
+} // 7222 bytes
+
+#[derive(Default, BorshDeserialize)]
+struct WithdrawalLogV0 {
+    is_initialized: bool,
+    vesting_type_account: Pubkey,
+    next_index: u32,
+    total_recorded: u64,
+    total_withdrawn: u64,
+    entries: [WithdrawalEntry; MAX_WITHDRAWAL_RECORDS],
+}
+
+impl Versioned for WithdrawalLog {
+    const VERSION: u8 = CURRENT_VERSION;
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            LEGACY_VERSION => {
+                let legacy = WithdrawalLogV0::deserialize(&mut &data[..])?;
+                Ok(WithdrawalLog {
+                    version: CURRENT_VERSION,
+                    is_initialized: legacy.is_initialized,
+                    vesting_type_account: legacy.vesting_type_account,
+                    next_index: legacy.next_index,
+                    total_recorded: legacy.total_recorded,
+                    total_withdrawn: legacy.total_withdrawn,
+                    entries: legacy.entries,
+                })
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl WithdrawalLog {
+    /// Byte offset of `next_index` within the account's serialized data —
+    /// `version` + `is_initialized` + `vesting_type_account`.
+    const NEXT_INDEX_OFFSET: usize = 1 + 1 + 32;
+    /// Byte offset of `entries[0]` — the header in full.
+    const ENTRIES_OFFSET: usize = Self::NEXT_INDEX_OFFSET + 4 + 8 + 8;
+    const ENTRY_LEN: usize = 8 + 8 + 32 + 8;
+
+    /// Appends `entry` into the ring buffer, persisting just that entry's
+    /// slot plus the small cursor/total header rather than re-serializing
+    /// the whole (potentially large) `entries` array on every withdrawal.
+    /// `entry.running_total` is overwritten with the cumulative total after
+    /// this withdrawal — callers need not (and can't reliably) compute it
+    /// themselves.
+    pub fn record(&mut self, account: &AccountInfo, mut entry: WithdrawalEntry) -> ProgramResult {
+        self.total_recorded += 1;
+        self.total_withdrawn += entry.amount;
+        entry.running_total = self.total_withdrawn;
+
+        let index = self.next_index as usize % MAX_WITHDRAWAL_RECORDS;
+        self.entries[index] = entry;
+        self.next_index = self.next_index.wrapping_add(1);
+
+        write_to_storage_at_offset(
+            &entry,
+            Self::ENTRIES_OFFSET + index * Self::ENTRY_LEN,
+            account,
+        )?;
+        write_to_storage_at_offset(
+            &(self.next_index, self.total_recorded, self.total_withdrawn),
+            Self::NEXT_INDEX_OFFSET,
+            account,
+        )
+    }
+
+    /// Appends `entry` only if this log has actually been set up for
+    /// `vesting_type` via `InitWithdrawalLog`; a caller that doesn't use the
+    /// audit log feature can pass any uninitialized account here and nothing
+    /// is written. An initialized log whose `vesting_type_account` doesn't
+    /// match is rejected outright, since that means the wrong account was
+    /// passed rather than the feature being unused.
+    pub fn record_if_configured(
+        account: &AccountInfo,
+        vesting_type: &Pubkey,
+        entry: WithdrawalEntry,
+    ) -> ProgramResult {
+        let mut log = read_from_storage::<WithdrawalLog>(account)?;
+        if !log.is_initialized {
+            return Ok(());
+        }
+        if &log.vesting_type_account != vesting_type {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        log.record(account, entry)
+    }
+}
+
+/// An external on-chain condition that must additionally be satisfied
+/// before a `VestingAccount`'s unlocked tokens may be withdrawn, e.g. a
+/// staking or governance program that still has the beneficiary's tokens
+/// locked up. Checked via a CPI into `program`, passing `metadata`, from
+/// `withdraw_from_vesting`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Realizor {
+    pub program: Pubkey,
+    pub metadata: Pubkey,
+}
+
+#[derive(Default, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct VestingAccount {
+    pub version: u8,                     // 1
+    pub is_initialized: bool,           // 1
+    pub total_tokens: u64,              // 8
+    pub withdrawn_tokens: u64,          // 8
+    pub token_account: Pubkey,          // 32
+    pub vesting_type_account: Pubkey,   // 32
+    pub revoked: bool,                  // 1
+    pub realizor: Option<Realizor>,     // 1 + 64
+    pub whitelisted_tokens: u64,        // 8
+    pub window_start: u64,              // 8
+    pub withdrawn_in_window: u64,       // 8
+    pub last_withdraw_ts: i64,          // 8
+} // 180 bytes
+
+#[derive(Default, BorshDeserialize)]
+struct VestingAccountV0 {
+    is_initialized: bool,
+    total_tokens: u64,
+    withdrawn_tokens: u64,
+    token_account: Pubkey,
+    vesting_type_account: Pubkey,
+    revoked: bool,
+}
+
+/// Shape of `VestingAccount` as of the `Versioned` introduction, before the
+/// `realizor` claim-lock hook was added.
+#[derive(Default, BorshDeserialize)]
+struct VestingAccountV1 {
+    version: u8,
+    is_initialized: bool,
+    total_tokens: u64,
+    withdrawn_tokens: u64,
+    token_account: Pubkey,
+    vesting_type_account: Pubkey,
+    revoked: bool,
+}
+
+/// Shape of `VestingAccount` after the `realizor` claim-lock hook was added,
+/// before `whitelisted_tokens` tracked tokens moved out via `WhitelistWithdraw`.
+#[derive(Default, BorshDeserialize)]
+struct VestingAccountV2 {
+    version: u8,
+    is_initialized: bool,
+    total_tokens: u64,
+    withdrawn_tokens: u64,
+    token_account: Pubkey,
+    vesting_type_account: Pubkey,
+    revoked: bool,
+    realizor: Option<Realizor>,
+}
+
+/// Shape of `VestingAccount` before the per-window withdrawal rate limit
+/// added `window_start`/`withdrawn_in_window`, i.e. every account that has
+/// never had a withdrawal counted against a rate-limit window.
+#[derive(Default, BorshDeserialize)]
+struct VestingAccountV3 {
+    version: u8,
+    is_initialized: bool,
+    total_tokens: u64,
+    withdrawn_tokens: u64,
+    token_account: Pubkey,
+    vesting_type_account: Pubkey,
+    revoked: bool,
+    realizor: Option<Realizor>,
+    whitelisted_tokens: u64,
+}
+
+/// Shape of `VestingAccount` before it gained `last_withdraw_ts` (the
+/// per-account withdrawal timelock cursor), i.e. every account whose
+/// withdrawals were never gated by `VestingTypeAccount::withdrawal_timelock`.
+#[derive(Default, BorshDeserialize)]
+struct VestingAccountV4 {
+    version: u8,
+    is_initialized: bool,
+    total_tokens: u64,
+    withdrawn_tokens: u64,
+    token_account: Pubkey,
+    vesting_type_account: Pubkey,
+    revoked: bool,
+    realizor: Option<Realizor>,
+    whitelisted_tokens: u64,
+    window_start: u64,
+    withdrawn_in_window: u64,
+}
+
+impl Versioned for VestingAccount {
+    // Bumped past `CURRENT_VERSION` because this struct alone gained the
+    // `whitelisted_tokens` field, then `window_start`/`withdrawn_in_window`,
+    // then `last_withdraw_ts`; the other account types are untouched by
+    // these changes.
+    const VERSION: u8 = 5;
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            LEGACY_VERSION => {
+                let legacy = VestingAccountV0::deserialize(&mut &data[..])?;
+                Ok(VestingAccount {
+                    version: Self::VERSION,
+                    is_initialized: legacy.is_initialized,
+                    total_tokens: legacy.total_tokens,
+                    withdrawn_tokens: legacy.withdrawn_tokens,
+                    token_account: legacy.token_account,
+                    vesting_type_account: legacy.vesting_type_account,
+                    revoked: legacy.revoked,
+                    realizor: None,
+                    whitelisted_tokens: 0,
+                    window_start: 0,
+                    withdrawn_in_window: 0,
+                    last_withdraw_ts: 0,
+                })
+            }
+            1 => {
+                let previous = VestingAccountV1::deserialize(&mut &data[..])?;
+                Ok(VestingAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    total_tokens: previous.total_tokens,
+                    withdrawn_tokens: previous.withdrawn_tokens,
+                    token_account: previous.token_account,
+                    vesting_type_account: previous.vesting_type_account,
+                    revoked: previous.revoked,
+                    realizor: None,
+                    whitelisted_tokens: 0,
+                    window_start: 0,
+                    withdrawn_in_window: 0,
+                    last_withdraw_ts: 0,
+                })
+            }
+            2 => {
+                let previous = VestingAccountV2::deserialize(&mut &data[..])?;
+                Ok(VestingAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    total_tokens: previous.total_tokens,
+                    withdrawn_tokens: previous.withdrawn_tokens,
+                    token_account: previous.token_account,
+                    vesting_type_account: previous.vesting_type_account,
+                    revoked: previous.revoked,
+                    realizor: previous.realizor,
+                    whitelisted_tokens: 0,
+                    window_start: 0,
+                    withdrawn_in_window: 0,
+                    last_withdraw_ts: 0,
+                })
+            }
+            3 => {
+                let previous = VestingAccountV3::deserialize(&mut &data[..])?;
+                Ok(VestingAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    total_tokens: previous.total_tokens,
+                    withdrawn_tokens: previous.withdrawn_tokens,
+                    token_account: previous.token_account,
+                    vesting_type_account: previous.vesting_type_account,
+                    revoked: previous.revoked,
+                    realizor: previous.realizor,
+                    whitelisted_tokens: previous.whitelisted_tokens,
+                    window_start: 0,
+                    withdrawn_in_window: 0,
+                    last_withdraw_ts: 0,
+                })
+            }
+            4 => {
+                let previous = VestingAccountV4::deserialize(&mut &data[..])?;
+                Ok(VestingAccount {
+                    version: Self::VERSION,
+                    is_initialized: previous.is_initialized,
+                    total_tokens: previous.total_tokens,
+                    withdrawn_tokens: previous.withdrawn_tokens,
+                    token_account: previous.token_account,
+                    vesting_type_account: previous.vesting_type_account,
+                    revoked: previous.revoked,
+                    realizor: previous.realizor,
+                    whitelisted_tokens: previous.whitelisted_tokens,
+                    window_start: previous.window_start,
+                    withdrawn_in_window: previous.withdrawn_in_window,
+                    last_withdraw_ts: 0,
+                })
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl VestingAccount {
+    pub fn calculate_available_to_withdraw_amount(
+        &self,
+        schedule: &VestingSchedule,
+        now: u64,
+    ) -> u64 {
+        let unlocked_amount = schedule.available(now);
+        let unlocked_amount = unlocked_amount.min(self.total_tokens); // safeguard check
+        unlocked_amount.saturating_sub(self.withdrawn_tokens + self.whitelisted_tokens)
+    }
+
+    /// Like `calculate_available_to_withdraw_amount`, but additionally caps
+    /// the result so a single window of `schedule.min_period()` can never
+    /// release more than `schedule.withdrawal_cap()`, independent of how
+    /// much is vested. If `schedule` has no rate limit configured, this is
+    /// identical to `calculate_available_to_withdraw_amount`. Does not
+    /// itself roll the window over — call `record_withdrawal_for_rate_limit`
+    /// once a withdrawal of the returned (or smaller) amount actually
+    /// happens.
+    pub fn calculate_withdrawable_with_cap(&self, schedule: &VestingSchedule, now: u64) -> u64 {
+        let withdrawable = self.calculate_available_to_withdraw_amount(schedule, now);
+        match (schedule.min_period(), schedule.withdrawal_cap()) {
+            (Some(min_period), Some(withdrawal_cap)) => {
+                let withdrawn_in_window = if now.saturating_sub(self.window_start) >= min_period {
+                    0
+                } else {
+                    self.withdrawn_in_window
+                };
+                let remaining_cap = withdrawal_cap.saturating_sub(withdrawn_in_window);
+                withdrawable.min(remaining_cap)
+            }
+            _ => withdrawable,
+        }
+    }
+
+    /// Records a withdrawal of `amount` at `now` against the rate-limit
+    /// window, rolling the window over (resetting the per-window allowance)
+    /// if `schedule.min_period()` has elapsed since it started. A no-op
+    /// when `schedule` has no rate limit configured.
+    pub fn record_withdrawal_for_rate_limit(
+        &mut self,
+        schedule: &VestingSchedule,
+        amount: u64,
+        now: u64,
+    ) {
+        if let Some(min_period) = schedule.min_period() {
+            if now.saturating_sub(self.window_start) >= min_period {
+                self.window_start = now;
+                self.withdrawn_in_window = 0;
+            }
+            self.withdrawn_in_window += amount;
+        }
+    }
+
+    /// Terminates this account early, freezing it at `total_vested(now)`:
+    /// the beneficiary keeps (and may still withdraw, on the normal
+    /// schedule) everything vested as of `now`, while the returned amount
+    /// is the unvested remainder a terminator may reclaim. Lowering
+    /// `total_tokens` to the frozen vested amount is what makes the clamp
+    /// permanent — `calculate_available_to_withdraw_amount`'s existing
+    /// `.min(self.total_tokens)` then keeps the vested curve from ever
+    /// growing past this point, no matter how much later it's queried.
+    /// `whitelisted_tokens` have already been staked out of `token_pool`
+    /// via `WhitelistWithdraw` and are no longer sitting there to reclaim,
+    /// so they're subtracted out of the unvested remainder the same way
+    /// `sign_devesting`'s clawback does.
+    pub fn terminate(&mut self, schedule: &VestingSchedule, now: u64) -> u64 {
+        let vested = schedule.available(now).min(self.total_tokens);
+        let unvested = self
+            .total_tokens
+            .saturating_sub(vested)
+            .saturating_sub(self.whitelisted_tokens);
+        self.total_tokens = vested;
+        self.revoked = true;
+        unvested
+    }
+
+    /// Splits this account into two, so a grant can be divided between
+    /// beneficiaries. `fraction_tokens` becomes the first account's
+    /// `total_tokens`; the remainder becomes the second's. `withdrawn_tokens`
+    /// and `whitelisted_tokens` are divided in the same proportion, floored
+    /// with the same u128 rounding `LinearVesting::vested` uses, so the two
+    /// halves always sum back to the original with no dust.
+    pub fn split(
+        &self,
+        fraction_tokens: u64,
+    ) -> Result<(VestingAccount, VestingAccount), ProgramError> {
+        if fraction_tokens > self.total_tokens {
+            return Err(VestingError::NotEnoughTokensInPool.into());
+        }
+
+        let proportion = |amount: u64| -> u64 {
+            if self.total_tokens == 0 {
+                0
+            } else {
+                (amount as u128 * fraction_tokens as u128 / self.total_tokens as u128) as u64
+            }
+        };
+        let withdrawn_first = proportion(self.withdrawn_tokens);
+        let whitelisted_first = proportion(self.whitelisted_tokens);
+
+        let first = VestingAccount {
+            version: self.version,
+            is_initialized: self.is_initialized,
+            total_tokens: fraction_tokens,
+            withdrawn_tokens: withdrawn_first,
+            token_account: self.token_account,
+            vesting_type_account: self.vesting_type_account,
+            revoked: self.revoked,
+            realizor: self.realizor,
+            whitelisted_tokens: whitelisted_first,
+            // Each half starts its own rate-limit window and withdrawal
+            // timelock fresh rather than inheriting a proportional share of
+            // an in-flight one.
+            window_start: 0,
+            withdrawn_in_window: 0,
+            last_withdraw_ts: 0,
+        };
+        let second = VestingAccount {
+            version: self.version,
+            is_initialized: self.is_initialized,
+            total_tokens: self.total_tokens - fraction_tokens,
+            withdrawn_tokens: self.withdrawn_tokens - withdrawn_first,
+            token_account: self.token_account,
+            vesting_type_account: self.vesting_type_account,
+            revoked: self.revoked,
+            realizor: self.realizor,
+            whitelisted_tokens: self.whitelisted_tokens - whitelisted_first,
+            window_start: 0,
+            withdrawn_in_window: 0,
+            last_withdraw_ts: 0,
+        };
+
+        Ok((first, second))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_success() {
+        let cliff = 20_000;
+        let offseted_by = 30_000;
+        let standalone = 200_000;
+
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .cliff(cliff, Some(100_000))
+            .offseted_by(
+                offseted_by,
+                LinearVesting::without_start(10_000, 3),
+                Some(100_000),
+            )
+            .map(|x| x.offseted(LinearVesting::without_start(20_000, 5), Some(100_000)))
+            .and_then(|x| match x {
+                Err(e) => Err(e),
+                Ok(x) => Ok(x.add(LinearVesting::new(standalone, 10_000, 2), None)),
+            })
+            .and_then(|x| x.build());
+        assert!(schedule.is_ok());
+
+        let schedule = schedule.unwrap();
+        assert_eq!(schedule.total_tokens(), 1_000_000);
+        assert_eq!(
+            &schedule.vestings[..schedule.vesting_count as usize],
+            &[
+                (100_000, LinearVesting::cliff(cliff)),
+                (100_000, LinearVesting::new(cliff + offseted_by, 10_000, 3)),
+                (
+                    100_000,
+                    LinearVesting::new(cliff + offseted_by + 10_000 * (3 - 1) + 20_000, 20_000, 5)
+                ),
+                (700_000, LinearVesting::new(standalone, 10_000, 2)),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_builder_failure_offset() {
+        let schedule = VestingSchedule::with_tokens(1_000_000).offseted_by(
+            10_000,
+            LinearVesting::without_start(10_000, 3),
+            None,
+        );
+        assert_eq!(schedule, Err(ScheduleBuilderError::EmptyBuilder))
+    }
+
+    #[test]
+    fn test_builder_failure_remaining_tokens() {
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .cliff(10_000, Some(100_000))
+            .build();
+        assert_eq!(
+            schedule,
+            Err(ScheduleBuilderError::InvalidTokenAmountUsed((
+                1_000_000, 100_000
+            )))
+        )
+    }
+
+    #[test]
+    fn test_builder_failure_unsorted_vestings() {
         let schedule = VestingSchedule::with_tokens(1_000_000)
             .add(LinearVesting::new(10_000, 10_000, 3), Some(100_000))
             .add(LinearVesting::new(20_000, 10_000, 3), None)
@@ -501,12 +2295,230 @@ mod tests {
     }
 
     #[test]
-    fn test_builder_failure_zero_token() {
-        let schedule = VestingSchedule::with_tokens(1_000_000)
-            .add(LinearVesting::new(10_000, 10_000, 3), None)
-            .add(LinearVesting::new(50_000, 10_000, 3), None)
-            .build();
-        assert_eq!(schedule, Err(ScheduleBuilderError::ZeroTokens))
+    fn test_builder_failure_zero_token() {
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .add(LinearVesting::new(10_000, 10_000, 3), None)
+            .add(LinearVesting::new(50_000, 10_000, 3), None)
+            .build();
+        assert_eq!(schedule, Err(ScheduleBuilderError::ZeroTokens))
+    }
+
+    #[test]
+    fn test_linear_vesting_last_saturates_instead_of_panicking() {
+        let vesting = LinearVesting::new(u64::MAX - 10, u64::MAX, 2);
+        assert_eq!(vesting.last(), u64::MAX);
+    }
+
+    #[test]
+    fn test_builder_failure_duration_overflow_multiplication() {
+        // `unlock_period * (unlock_count - 1)` alone overflows a `u64`.
+        let schedule = VestingSchedule::with_tokens(100)
+            .add(LinearVesting::new(0, u64::MAX, 3), Some(100))
+            .build();
+        assert_eq!(schedule, Err(ScheduleBuilderError::DurationOverflow));
+    }
+
+    #[test]
+    fn test_builder_failure_duration_overflow_addition() {
+        // The span itself fits, but adding it to `start_time` doesn't.
+        let schedule = VestingSchedule::with_tokens(100)
+            .add(LinearVesting::new(u64::MAX - 10, 100, u8::MAX), Some(100))
+            .build();
+        assert_eq!(schedule, Err(ScheduleBuilderError::DurationOverflow));
+    }
+
+    #[test]
+    fn test_builder_failure_duration_exceeds_max_span() {
+        // No overflow here: the span is merely bigger than the policy allows.
+        let schedule = VestingSchedule::with_tokens(100)
+            .add(LinearVesting::new(0, VestingSchedule::MAX_UNLOCK_SPAN, 3), Some(100))
+            .build();
+        assert_eq!(schedule, Err(ScheduleBuilderError::DurationOverflow));
+    }
+
+    #[test]
+    fn test_builder_steps_success() {
+        let schedule = VestingSchedule::with_tokens(600)
+            .steps(&[(1_000, 100), (1_500, 250), (3_000, 250)])
+            .and_then(|x| x.build());
+        assert!(schedule.is_ok());
+
+        let schedule = schedule.unwrap();
+        assert_eq!(schedule.total_tokens(), 600);
+        assert_eq!(
+            schedule.vestings(),
+            &[
+                (100, LinearVesting::cliff(1_000)),
+                (250, LinearVesting::cliff(1_500)),
+                (250, LinearVesting::cliff(3_000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builder_steps_failure_unsorted_timestamps() {
+        let schedule = VestingSchedule::with_tokens(300).steps(&[(2_000, 100), (1_000, 200)]);
+        assert_eq!(schedule, Err(ScheduleBuilderError::VestingsNotSorted));
+    }
+
+    #[test]
+    fn test_builder_steps_failure_zero_amount() {
+        let schedule = VestingSchedule::with_tokens(300).steps(&[(1_000, 0), (2_000, 300)]);
+        assert_eq!(schedule, Err(ScheduleBuilderError::ZeroTokens));
+    }
+
+    #[test]
+    fn test_builder_steps_failure_too_many() {
+        let entries: Vec<(u64, u64)> = (0..=VestingSchedule::MAX_VESTINGS as u64)
+            .map(|i| (i * 100, 1))
+            .collect();
+        let schedule = VestingSchedule::with_tokens(entries.len() as u64).steps(&entries);
+        assert_eq!(schedule, Err(ScheduleBuilderError::TooManyVestings));
+    }
+
+    #[test]
+    fn test_builder_from_unlock_points_success() {
+        let schedule = VestingSchedule::with_tokens(600)
+            .from_unlock_points(&[(3_000, 250), (1_000, 100), (1_500, 250)])
+            .and_then(|x| x.build());
+        assert!(schedule.is_ok());
+
+        let schedule = schedule.unwrap();
+        assert_eq!(schedule.total_tokens(), 600);
+        assert_eq!(
+            schedule.vestings(),
+            &[
+                (100, LinearVesting::cliff(1_000)),
+                (250, LinearVesting::cliff(1_500)),
+                (250, LinearVesting::cliff(3_000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builder_from_unlock_points_merges_duplicate_timestamps() {
+        let schedule = VestingSchedule::with_tokens(600)
+            .from_unlock_points(&[(1_000, 100), (2_000, 200), (1_000, 300)])
+            .and_then(|x| x.build());
+        assert!(schedule.is_ok());
+
+        let schedule = schedule.unwrap();
+        assert_eq!(
+            schedule.vestings(),
+            &[
+                (400, LinearVesting::cliff(1_000)),
+                (200, LinearVesting::cliff(2_000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builder_from_unlock_points_failure_wrong_total() {
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .from_unlock_points(&[(1_000, 100_000)]);
+        assert_eq!(
+            schedule,
+            Err(ScheduleBuilderError::InvalidTokenAmountUsed((
+                1_000_000, 100_000
+            )))
+        );
+    }
+
+    #[test]
+    fn test_builder_from_unlock_points_failure_zero_amount() {
+        let schedule = VestingSchedule::with_tokens(300).from_unlock_points(&[(1_000, 0), (2_000, 300)]);
+        assert_eq!(schedule, Err(ScheduleBuilderError::ZeroTokens));
+    }
+
+    #[test]
+    fn test_builder_from_unlock_points_failure_too_many() {
+        let points: Vec<(u64, u64)> = (0..=VestingSchedule::MAX_VESTINGS as u64)
+            .map(|i| (i * 100, 1))
+            .collect();
+        let schedule =
+            VestingSchedule::with_tokens(points.len() as u64).from_unlock_points(&points);
+        assert_eq!(schedule, Err(ScheduleBuilderError::TooManyVestings));
+    }
+
+    #[test]
+    fn test_builder_checkpoints_success() {
+        let schedule = VestingSchedule::with_tokens(600)
+            .checkpoints(&[(1_000, 100), (1_500, 350), (3_000, 600)])
+            .and_then(|x| x.build());
+        assert!(schedule.is_ok());
+
+        let schedule = schedule.unwrap();
+        assert_eq!(schedule.total_tokens(), 600);
+        assert_eq!(
+            schedule.vestings(),
+            &[
+                (100, LinearVesting::cliff(1_000)),
+                (250, LinearVesting::cliff(1_500)),
+                (250, LinearVesting::cliff(3_000)),
+            ]
+        );
+
+        assert_eq!(schedule.available(999), 0);
+        assert_eq!(schedule.available(1_000), 100);
+        assert_eq!(schedule.available(1_499), 100);
+        assert_eq!(schedule.available(1_500), 350);
+        assert_eq!(schedule.available(2_999), 350);
+        assert_eq!(schedule.available(3_000), 600);
+        assert_eq!(schedule.available(u64::MAX), 600);
+    }
+
+    #[test]
+    fn test_builder_checkpoints_allows_repeated_cumulative_amount() {
+        // A checkpoint that doesn't add anything new (tie in cumulative
+        // amount) is a no-op, not an error.
+        let schedule = VestingSchedule::with_tokens(300)
+            .checkpoints(&[(1_000, 300), (2_000, 300)])
+            .and_then(|x| x.build());
+        assert!(schedule.is_ok());
+
+        let schedule = schedule.unwrap();
+        assert_eq!(schedule.vestings(), &[(300, LinearVesting::cliff(1_000))]);
+        assert_eq!(schedule.available(1_000), 300);
+        assert_eq!(schedule.available(2_000), 300);
+    }
+
+    #[test]
+    fn test_builder_checkpoints_failure_timestamps_not_strictly_increasing() {
+        let schedule =
+            VestingSchedule::with_tokens(300).checkpoints(&[(1_000, 100), (1_000, 300)]);
+        assert_eq!(schedule, Err(ScheduleBuilderError::VestingsNotSorted));
+    }
+
+    #[test]
+    fn test_builder_checkpoints_failure_amounts_decrease() {
+        let schedule =
+            VestingSchedule::with_tokens(300).checkpoints(&[(1_000, 300), (2_000, 100)]);
+        assert_eq!(
+            schedule,
+            Err(ScheduleBuilderError::CheckpointsNotNonDecreasing)
+        );
+    }
+
+    #[test]
+    fn test_builder_checkpoints_failure_wrong_total() {
+        let schedule =
+            VestingSchedule::with_tokens(1_000_000).checkpoints(&[(1_000, 100_000)]);
+        assert_eq!(
+            schedule,
+            Err(ScheduleBuilderError::InvalidTokenAmountUsed((
+                1_000_000, 100_000
+            )))
+        );
+    }
+
+    #[test]
+    fn test_builder_checkpoints_failure_too_many() {
+        let checkpoints: Vec<(u64, u64)> = (0..=VestingSchedule::MAX_VESTINGS as u64)
+            .map(|i| (i * 100, i + 1))
+            .collect();
+        let schedule = VestingSchedule::with_tokens(checkpoints.last().unwrap().1)
+            .checkpoints(&checkpoints);
+        assert_eq!(schedule, Err(ScheduleBuilderError::TooManyVestings));
     }
 
     #[test]
@@ -544,50 +2556,153 @@ mod tests {
     }
 
     #[test]
-    fn test_vesting_cliff_available_tokens() {
+    fn test_vesting_cliff_vested_tokens() {
         let start_time = 100;
         let vesting = LinearVesting::cliff(100);
+        let tokens = 1_000;
 
         assert_eq!(vesting.last(), start_time);
-        assert_eq!(vesting.part(), 1.0);
 
-        assert_eq!(vesting.available(u64::MIN), 0.0);
-        assert_eq!(vesting.available(start_time - 10), 0.0);
-        assert_eq!(vesting.available(start_time), 1.0);
-        assert_eq!(vesting.available(start_time + 10), 1.0);
-        assert_eq!(vesting.available(u64::MAX), 1.0);
+        assert_eq!(vesting.vested(tokens, u64::MIN), 0);
+        assert_eq!(vesting.vested(tokens, start_time - 10), 0);
+        assert_eq!(vesting.vested(tokens, start_time), tokens);
+        assert_eq!(vesting.vested(tokens, start_time + 10), tokens);
+        assert_eq!(vesting.vested(tokens, u64::MAX), tokens);
     }
 
     #[test]
-    fn test_vesting_available_tokens() {
+    fn test_vesting_vested_tokens() {
         let start_time = 100;
         let period = 10;
         let unlocks = 7;
+        // Evenly divisible by `unlocks` so every period unlocks the same
+        // exact amount, with no floor-rounding remainder to account for.
+        let tokens = 700;
         let vesting = LinearVesting::new(start_time, period, unlocks);
 
         assert_eq!(vesting.last(), start_time + period * (unlocks - 1) as u64);
-        assert_eq!(vesting.part(), 1.0 / unlocks as f64);
+        assert_eq!(vesting.vested(tokens, u64::MIN), 0);
 
-        assert_eq!(vesting.available(u64::MIN), 0.0);
+        for i in 1..=unlocks {
+            let time = start_time + (i - 1) as u64 * period;
+            let previous = tokens * (i - 1) as u64 / unlocks as u64;
+            let current = tokens * i as u64 / unlocks as u64;
+            assert_eq!(vesting.vested(tokens, time - period / 2), previous);
+            assert_eq!(vesting.vested(tokens, time), current);
+            assert_eq!(vesting.vested(tokens, time + period / 2), current);
+        }
+        assert_eq!(vesting.vested(tokens, u64::MAX), tokens);
+    }
 
-        let almost_eq = |a: f64, b: f64| (a - b).abs() < 0.0001;
+    #[test]
+    fn test_vesting_vested_tokens_with_remainder() {
+        let start_time = 100;
+        let period = 10;
+        let unlocks = 7;
+        // Not evenly divisible by `unlocks`: exercises the floor-the-
+        // unvested-remainder rounding direction at every period boundary.
+        let tokens = 1_000;
+        let vesting = LinearVesting::new(start_time, period, unlocks);
 
-        for i in 1..=unlocks {
-            let time = start_time + (i - 1) as u64 * 10;
-            assert!(almost_eq(
-                vesting.available(time - period / 2),
-                vesting.part() * i as f64 - vesting.part()
-            ));
-            assert!(almost_eq(
-                vesting.available(time),
-                vesting.part() * i as f64
-            ));
-            assert!(almost_eq(
-                vesting.available(time + period / 2),
-                vesting.part() * i as f64
-            ));
+        let expected_per_period = [143, 286, 429, 572, 715, 858, 1_000];
+        for (index, &expected) in expected_per_period.iter().enumerate() {
+            let periods_passed = index as u64 + 1;
+            let time = start_time + (periods_passed - 1) * period;
+            assert_eq!(vesting.vested(tokens, time), expected);
+            if periods_passed < unlocks as u64 {
+                assert_eq!(vesting.vested(tokens, time + period / 2), expected);
+            }
         }
-        assert_eq!(vesting.available(u64::MAX), 1.0);
+        assert_eq!(vesting.vested(tokens, u64::MAX), tokens);
+    }
+
+    #[test]
+    fn test_vesting_continuous_vested_tokens() {
+        let start_time = 1_000;
+        let end_time = 5_000;
+        let tokens = 1_000_000;
+        let vesting = LinearVesting::continuous(start_time, end_time);
+
+        assert_eq!(vesting.last(), end_time);
+        assert_eq!(vesting.vested(tokens, u64::MIN), 0);
+        assert_eq!(vesting.vested(tokens, start_time - 1), 0);
+        assert_eq!(vesting.vested(tokens, start_time), 0);
+        assert_eq!(
+            vesting.vested(tokens, start_time + (end_time - start_time) / 4),
+            tokens / 4
+        );
+        assert_eq!(
+            vesting.vested(tokens, start_time + (end_time - start_time) / 2),
+            tokens / 2
+        );
+        assert_eq!(vesting.vested(tokens, end_time), tokens);
+        assert_eq!(vesting.vested(tokens, end_time + 1), tokens);
+        assert_eq!(vesting.vested(tokens, u64::MAX), tokens);
+    }
+
+    #[test]
+    fn test_vesting_continuous_vested_tokens_no_overflow_near_u64_max() {
+        // Large token amounts and a wide time span must not overflow the
+        // intermediate multiplication before the division.
+        let start_time = 0;
+        let end_time = u64::MAX / 2;
+        let tokens = u64::MAX;
+        let vesting = LinearVesting::continuous(start_time, end_time);
+
+        let elapsed = end_time / 2;
+        let expected = (tokens as u128 * elapsed as u128 / end_time as u128) as u64;
+        assert_eq!(vesting.vested(tokens, elapsed), expected);
+        assert_eq!(vesting.vested(tokens, end_time), tokens);
+    }
+
+    #[test]
+    fn test_vesting_daily_vested_tokens() {
+        let start_time = 1_000;
+        let total_days = 4;
+        let tokens = 1_000_000;
+        let vesting = LinearVesting::daily(start_time, total_days);
+
+        assert_eq!(vesting.last(), start_time + total_days as u64 * SECS_PER_DAY);
+        // Unlike `new`, no unit has fully elapsed yet at `start_time` itself.
+        assert_eq!(vesting.vested(tokens, start_time), 0);
+        assert_eq!(vesting.vested(tokens, start_time + SECS_PER_DAY - 1), 0);
+        assert_eq!(vesting.vested(tokens, start_time + SECS_PER_DAY), tokens / 4);
+        assert_eq!(
+            vesting.vested(tokens, start_time + 2 * SECS_PER_DAY),
+            tokens / 2
+        );
+        assert_eq!(
+            vesting.vested(tokens, start_time + 4 * SECS_PER_DAY),
+            tokens
+        );
+        assert_eq!(vesting.vested(tokens, u64::MAX), tokens);
+    }
+
+    #[test]
+    fn test_vesting_monthly_vested_tokens() {
+        let start_time = 1_000;
+        let total_months = 12;
+        let tokens = 1_200_000;
+        let vesting = LinearVesting::monthly(start_time, total_months);
+
+        assert_eq!(
+            vesting.last(),
+            start_time + total_months as u64 * SECS_PER_MONTH
+        );
+        assert_eq!(vesting.vested(tokens, start_time), 0);
+        assert_eq!(
+            vesting.vested(tokens, start_time + SECS_PER_MONTH),
+            tokens / 12
+        );
+        assert_eq!(
+            vesting.vested(tokens, start_time + 6 * SECS_PER_MONTH),
+            tokens / 2
+        );
+        assert_eq!(
+            vesting.vested(tokens, start_time + 12 * SECS_PER_MONTH),
+            tokens
+        );
+        assert_eq!(vesting.vested(tokens, u64::MAX), tokens);
     }
 
     #[test]
@@ -668,6 +2783,326 @@ mod tests {
         assert_eq!(schedule.available(u64::MAX), total_tokens);
     }
 
+    #[test]
+    fn test_schedule_cliff_ts_gates_availability() {
+        let start_time = 1_000;
+        let unlock_period = 1_000;
+        let unlock_count = 4;
+        let tokens = 1_000_000;
+        let cliff_ts = start_time + 2 * unlock_period;
+
+        let gated = VestingSchedule::with_tokens(tokens)
+            .cliff_ts(cliff_ts)
+            .add(
+                LinearVesting::new(start_time, unlock_period, unlock_count),
+                Some(tokens),
+            )
+            .build()
+            .unwrap();
+        let ungated = VestingSchedule::with_tokens(tokens)
+            .add(
+                LinearVesting::new(start_time, unlock_period, unlock_count),
+                Some(tokens),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(gated.cliff_ts(), Some(cliff_ts));
+        // Before the cliff, nothing is available even though the underlying
+        // curve would otherwise have released a partial amount.
+        assert_eq!(gated.available(start_time + unlock_period), 0);
+        assert_ne!(ungated.available(start_time + unlock_period), 0);
+
+        // From the cliff onward, the gate disappears entirely: the gated and
+        // ungated schedules report exactly the same amount, with nothing
+        // extra credited for the time spent behind the gate.
+        for time in [cliff_ts, cliff_ts + 1, start_time + 3 * unlock_period, u64::MAX] {
+            assert_eq!(gated.available(time), ungated.available(time));
+        }
+    }
+
+    #[test]
+    fn test_schedule_merge() {
+        let first = VestingSchedule::with_tokens(300)
+            .cliff(1_000, Some(100))
+            .add(LinearVesting::new(2_000, 1_000, 2), None)
+            .build()
+            .unwrap();
+        let second = VestingSchedule::with_tokens(300)
+            .cliff(1_500, Some(150))
+            .add(LinearVesting::new(5_000, 1_000, 2), None)
+            .build()
+            .unwrap();
+
+        let merged = first.merge(&second).unwrap();
+        assert_eq!(merged.total_tokens(), 600);
+
+        for time in [0, 1_000, 1_500, 2_000, 3_000, 5_000, 6_000, u64::MAX] {
+            assert_eq!(
+                merged.available(time),
+                first.available(time) + second.available(time)
+            );
+        }
+    }
+
+    #[test]
+    fn test_schedule_merge_failure_overlap() {
+        let first = VestingSchedule::with_tokens(100)
+            .add(LinearVesting::new(1_000, 500, 3), None)
+            .build()
+            .unwrap();
+        let second = VestingSchedule::with_tokens(50)
+            .cliff(1_500, None)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            first.merge(&second),
+            Err(ScheduleBuilderError::VestingsNotSorted)
+        );
+    }
+
+    #[test]
+    fn test_schedule_locked_and_voting_power() {
+        let start_time = 1_000;
+        let period = 1_000;
+        let unlock_count = 4;
+        let tokens = 800;
+        let saturation_secs = 2_000;
+
+        let schedule = VestingSchedule::with_tokens(tokens)
+            .add(LinearVesting::new(start_time, period, unlock_count), None)
+            .build()
+            .unwrap();
+        let last = schedule.last();
+        assert_eq!(last, start_time + period * (unlock_count - 1) as u64);
+
+        // Before start: fully locked, remaining lock duration saturates.
+        assert_eq!(schedule.locked(0), tokens);
+        assert_eq!(schedule.voting_power(0, saturation_secs), tokens);
+
+        // Mid-schedule: half vested, remaining lock duration under saturation.
+        let mid_time = 2_500;
+        assert_eq!(schedule.locked(mid_time), 400);
+        assert_eq!(
+            schedule.voting_power(mid_time, saturation_secs),
+            400 * (last - mid_time) / saturation_secs
+        );
+
+        // Past `last()`: fully vested, no voting power left.
+        assert_eq!(schedule.locked(last), 0);
+        assert_eq!(schedule.voting_power(last, saturation_secs), 0);
+        assert_eq!(schedule.locked(u64::MAX), 0);
+        assert_eq!(schedule.voting_power(u64::MAX, saturation_secs), 0);
+    }
+
+    #[test]
+    fn test_vesting_account_split() {
+        let account = VestingAccount {
+            total_tokens: 1_000_000,
+            withdrawn_tokens: 300_000,
+            whitelisted_tokens: 100_000,
+            ..Default::default()
+        };
+
+        let (first, second) = account.split(400_000).unwrap();
+        assert_eq!(first.total_tokens, 400_000);
+        assert_eq!(first.withdrawn_tokens, 120_000);
+        assert_eq!(first.whitelisted_tokens, 40_000);
+
+        assert_eq!(second.total_tokens, 600_000);
+        assert_eq!(second.withdrawn_tokens, 180_000);
+        assert_eq!(second.whitelisted_tokens, 60_000);
+
+        assert_eq!(
+            first.total_tokens + second.total_tokens,
+            account.total_tokens
+        );
+        assert_eq!(
+            first.withdrawn_tokens + second.withdrawn_tokens,
+            account.withdrawn_tokens
+        );
+        assert_eq!(
+            first.whitelisted_tokens + second.whitelisted_tokens,
+            account.whitelisted_tokens
+        );
+    }
+
+    #[test]
+    fn test_vesting_account_split_failure_fraction_too_big() {
+        let account = VestingAccount {
+            total_tokens: 100,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            account.split(101).err(),
+            Some(VestingError::NotEnoughTokensInPool.into())
+        );
+    }
+
+    #[test]
+    fn test_vesting_account_terminate_before_first_period() {
+        let start_time = 1_000;
+        let unlock_period = 1_000;
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .add(LinearVesting::new(start_time, unlock_period, 4), Some(1_000_000))
+            .build()
+            .unwrap();
+        let mut account = VestingAccount {
+            total_tokens: 1_000_000,
+            ..Default::default()
+        };
+
+        let unvested = account.terminate(&schedule, start_time - 1);
+        assert_eq!(unvested, 1_000_000);
+        assert_eq!(account.total_tokens, 0);
+        assert!(account.revoked);
+        assert_eq!(
+            account.calculate_available_to_withdraw_amount(&schedule, start_time - 1),
+            0
+        );
+        // The clamp holds even once the underlying schedule would otherwise
+        // have released everything.
+        assert_eq!(
+            account.calculate_available_to_withdraw_amount(&schedule, u64::MAX),
+            0
+        );
+    }
+
+    #[test]
+    fn test_vesting_account_terminate_between_periods() {
+        let start_time = 1_000;
+        let unlock_period = 1_000;
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .add(LinearVesting::new(start_time, unlock_period, 4), Some(1_000_000))
+            .build()
+            .unwrap();
+        let mut account = VestingAccount {
+            total_tokens: 1_000_000,
+            ..Default::default()
+        };
+
+        let terminated_at = start_time + unlock_period * 2;
+        let vested = schedule.available(terminated_at);
+        let unvested = account.terminate(&schedule, terminated_at);
+
+        assert_eq!(unvested, 1_000_000 - vested);
+        assert_eq!(account.total_tokens, vested);
+        assert_eq!(
+            account.calculate_available_to_withdraw_amount(&schedule, terminated_at),
+            vested
+        );
+        // Time passing after termination must not grow the curve further,
+        // even though the un-terminated schedule would have kept unlocking.
+        assert_eq!(
+            account.calculate_available_to_withdraw_amount(
+                &schedule,
+                terminated_at + unlock_period
+            ),
+            vested
+        );
+        assert_eq!(
+            account.calculate_available_to_withdraw_amount(&schedule, u64::MAX),
+            vested
+        );
+    }
+
+    #[test]
+    fn test_vesting_account_terminate_after_full_vest() {
+        let start_time = 1_000;
+        let unlock_period = 1_000;
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .add(LinearVesting::new(start_time, unlock_period, 4), Some(1_000_000))
+            .build()
+            .unwrap();
+        let mut account = VestingAccount {
+            total_tokens: 1_000_000,
+            ..Default::default()
+        };
+
+        let unvested = account.terminate(&schedule, schedule.last());
+        assert_eq!(unvested, 0);
+        assert_eq!(account.total_tokens, 1_000_000);
+        assert_eq!(
+            account.calculate_available_to_withdraw_amount(&schedule, u64::MAX),
+            1_000_000
+        );
+    }
+
+    #[test]
+    fn test_withdrawable_with_cap_no_limit_configured_matches_uncapped() {
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .add(LinearVesting::new(0, 1_000, 4), Some(1_000_000))
+            .build()
+            .unwrap();
+        let account = VestingAccount {
+            total_tokens: 1_000_000,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            account.calculate_withdrawable_with_cap(&schedule, schedule.last()),
+            account.calculate_available_to_withdraw_amount(&schedule, schedule.last())
+        );
+    }
+
+    #[test]
+    fn test_withdrawable_with_cap_clamps_below_vested_amount() {
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .add(LinearVesting::new(0, 1_000, 4), Some(1_000_000))
+            .withdrawal_limit(1_000, 100_000)
+            .build()
+            .unwrap();
+        let account = VestingAccount {
+            total_tokens: 1_000_000,
+            ..Default::default()
+        };
+
+        // Fully vested, but the window cap is far below that.
+        assert_eq!(
+            account.calculate_withdrawable_with_cap(&schedule, schedule.last()),
+            100_000
+        );
+    }
+
+    #[test]
+    fn test_withdrawable_with_cap_exhausts_across_window_then_resets() {
+        let schedule = VestingSchedule::with_tokens(1_000_000)
+            .add(LinearVesting::new(0, 1_000, 1), Some(1_000_000))
+            .withdrawal_limit(1_000, 100_000)
+            .build()
+            .unwrap();
+        let mut account = VestingAccount {
+            total_tokens: 1_000_000,
+            ..Default::default()
+        };
+
+        let now = schedule.last();
+        assert_eq!(
+            account.calculate_withdrawable_with_cap(&schedule, now),
+            100_000
+        );
+        account.record_withdrawal_for_rate_limit(&schedule, 60_000, now);
+        account.withdrawn_tokens += 60_000;
+        assert_eq!(
+            account.calculate_withdrawable_with_cap(&schedule, now),
+            40_000
+        );
+
+        account.record_withdrawal_for_rate_limit(&schedule, 40_000, now);
+        account.withdrawn_tokens += 40_000;
+        assert_eq!(account.calculate_withdrawable_with_cap(&schedule, now), 0);
+
+        // Once `min_period` has elapsed since the window started, the
+        // allowance resets even though nothing further has vested.
+        let next_window = now + 1_000;
+        assert_eq!(
+            account.calculate_withdrawable_with_cap(&schedule, next_window),
+            100_000
+        );
+    }
+
     fn construct_test_data() -> (VestingAccount, VestingSchedule) {
         let total_tokens = 1_000_000;
         let vesting = VestingAccount {
@@ -748,4 +3183,67 @@ mod tests {
             700_000
         );
     }
+
+    fn construct_vesting_type(schedule: VestingSchedule) -> VestingTypeAccount {
+        VestingTypeAccount {
+            is_initialized: true,
+            vesting_schedule: schedule,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_update_schedule_success() {
+        let (_, schedule) = construct_test_data();
+        let mut vesting_type = construct_vesting_type(schedule);
+
+        let new_schedule = VestingSchedule::with_tokens(1_000_000)
+            .cliff(1_000_000, Some(200_000))
+            .cliff(1_100_000, Some(200_000))
+            .add(LinearVesting::new(1_400_000, 400_000, 3), None)
+            .ending_at(3_000_000)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(vesting_type.update_schedule(new_schedule.clone(), 1_050_000).is_ok());
+        assert_eq!(vesting_type.vesting_schedule, new_schedule);
+    }
+
+    #[test]
+    fn test_update_schedule_failure_total_tokens_mismatch() {
+        let (_, schedule) = construct_test_data();
+        let mut vesting_type = construct_vesting_type(schedule);
+
+        let new_schedule = VestingSchedule::with_tokens(500_000)
+            .cliff(1_000_000, Some(500_000))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            vesting_type.update_schedule(new_schedule, 1_050_000),
+            Err(ScheduleBuilderError::TotalTokensMismatch)
+        );
+    }
+
+    #[test]
+    fn test_update_schedule_failure_clawback() {
+        let (_, schedule) = construct_test_data();
+        let mut vesting_type = construct_vesting_type(schedule);
+
+        // Delays the first cliff past `now`, so the amount unlocked as of
+        // `now` would go down relative to the schedule it replaces.
+        let new_schedule = VestingSchedule::with_tokens(1_000_000)
+            .cliff(1_100_000, Some(400_000))
+            .add(LinearVesting::new(1_400_000, 400_000, 3), None)
+            .ending_at(2_000_000)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            vesting_type.update_schedule(new_schedule, 1_050_000),
+            Err(ScheduleBuilderError::WouldClawBackUnlockedTokens)
+        );
+    }
 }
@@ -1,13 +1,85 @@
-use borsh::BorshSerialize;
-use solana_program::{account_info::AccountInfo, entrypoint_deprecated::ProgramResult};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, entrypoint_deprecated::ProgramResult, program_error::ProgramError,
+};
 
-pub fn write_to_storage<T>(data: T, account: &AccountInfo) -> ProgramResult
+/// Implemented by every account struct persisted through `write_to_storage` /
+/// `read_from_storage`. The one-byte version tag is serialized as each
+/// struct's first field, so `read_from_storage` can inspect it before
+/// committing to a layout and upgrade older accounts in place via `migrate`.
+pub trait Versioned: Sized {
+    const VERSION: u8;
+
+    fn set_version(&mut self, version: u8);
+
+    /// Reconstructs `Self` from account data serialized under an older
+    /// `version`. Implementors should match on every version older than
+    /// `VERSION` they still need to support.
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError>;
+}
+
+pub fn write_to_storage<T>(mut data: T, account: &AccountInfo) -> ProgramResult
 where
-    T: BorshSerialize,
+    T: Versioned + BorshSerialize,
 {
+    data.set_version(T::VERSION);
     let bytes = data.try_to_vec()?;
     let mut storage = account.try_borrow_mut_data()?;
     storage[0..bytes.len()].clone_from_slice(&bytes);
+    // A shorter re-serialization must not leave stale bytes from a previous,
+    // longer write lying around for the next deserialization to trip over.
+    for byte in storage[bytes.len()..].iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(())
+}
+
+pub fn read_from_storage<T>(account: &AccountInfo) -> Result<T, ProgramError>
+where
+    T: Versioned + BorshDeserialize,
+{
+    let data = account.try_borrow_data()?;
+    let version = *data.first().ok_or(ProgramError::InvalidAccountData)?;
+
+    if version == T::VERSION {
+        Ok(T::try_from_slice(&data)?)
+    } else {
+        T::migrate(version, &data)
+    }
+}
+
+/// Writes `data` at a byte `offset` within `account`'s storage, instead of
+/// always at offset 0 — for append-only structures like `WithdrawalLog`
+/// where most of the account is a fixed-capacity buffer and a write only
+/// ever touches its own entry plus a small header, not the whole thing.
+pub fn write_to_storage_at_offset<T>(
+    data: &T,
+    offset: usize,
+    account: &AccountInfo,
+) -> ProgramResult
+where
+    T: BorshSerialize,
+{
+    let bytes = data.try_to_vec()?;
+    let mut storage = account.try_borrow_mut_data()?;
+    storage[offset..offset + bytes.len()].clone_from_slice(&bytes);
+
+    Ok(())
+}
+
+/// Rejects `accounts` unless every pubkey in the slice is distinct. Solana
+/// lets a caller pass the same account into multiple instruction slots;
+/// processors that read one account's balance before transferring into
+/// another must call this on any pair that's required to differ, otherwise
+/// an aliased pair can desynchronize a balance read from the write that
+/// follows it.
+pub fn assert_distinct(accounts: &[&AccountInfo]) -> ProgramResult {
+    for (index, account) in accounts.iter().enumerate() {
+        if accounts[..index].iter().any(|other| other.key == account.key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
 
     Ok(())
 }